@@ -7,7 +7,7 @@ mod cli;
 mod commands;
 
 fn main() -> Result<()> {
-    Ok(run(cli::app())?)
+    run(cli::app())
 }
 
 /// Execute a cli app. This function parses the command line arguments and
@@ -16,13 +16,16 @@ fn run(app: clap::App<'static, 'static>) -> Result<()> {
     match app.get_matches().subcommand() {
         ("pre-commit", Some(_)) => match commands::pre_commit::exec() {
             Ok(_) => Ok(()),
-            Err(e) => Ok(eprintln!(
-                r#"
+            Err(e) => {
+                eprintln!(
+                    r#"
 info: `pre-commit` failed with
  {error}
 "#,
-                error = e
-            )),
+                    error = e
+                );
+                Ok(())
+            }
         },
         _ => {
             exit(1);
@@ -0,0 +1,39 @@
+//! Sources of the current time.
+//!
+//! Time-dependent building functions (i.e. `Url::expires_in_with()`) take a
+//! `Clock` so that callers, and tests in particular, can control what "now"
+//! means.
+use std::time::SystemTime;
+
+/// A source of the current time.
+///
+/// Any `Fn() -> SystemTime` is a `Clock`, which makes it easy to inject a
+/// fixed time:
+///
+/// ```
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use imgix::Clock;
+///
+/// let fixed = || UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+/// assert_eq!(fixed.now(), UNIX_EPOCH + Duration::from_secs(1_600_000_000));
+/// ```
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The system's clock, i.e. `SystemTime::now()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl<F: Fn() -> SystemTime> Clock for F {
+    fn now(&self) -> SystemTime {
+        self()
+    }
+}
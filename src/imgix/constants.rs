@@ -26,7 +26,7 @@ pub const SRCSET_TARGET_DPR_RATIOS: [u32; 5] = [1, 2, 3, 4, 5];
 pub const SRCSET_DPR_QUALITIES: [u32; 5] = [75, 50, 35, 23, 20];
 
 pub fn lib_version() -> String {
-    return format!("rust={}", env!("CARGO_PKG_VERSION"));
+    format!("rust={}", env!("CARGO_PKG_VERSION"))
 }
 
 #[cfg(test)]
@@ -0,0 +1,250 @@
+//! Percent-encoding for the components of an imgix URL.
+//!
+//! imgix expects paths to be encoded as JavaScript's `encodeURI` would
+//! encode them, with the additional encoding of `#`, `?`, `:` and `+`.
+//! Query keys and values are expected to be encoded as `encodeURIComponent`
+//! would encode them, except for the values of base64 parameter variants
+//! (i.e. `txt64`) which are expected to be URL-safe base64 encoded.
+//!
+//! The inverse functions, `decode` and `decode_base64`, are used when
+//! parsing existing imgix URLs.
+use std::fmt::Write;
+
+use crate::util::errors::Error::ParseError;
+use crate::Result;
+
+/// Characters, other than ASCII alphanumerics, left as-is by `component`.
+const COMPONENT_SAFE: &[u8] = b"-_.!~*'()";
+
+/// Characters, other than ASCII alphanumerics, left as-is by `path`.
+const PATH_SAFE: &[u8] = b"-_.!~*'();,/@&=$";
+
+/// Encode an image path (i.e. "images/my image.png").
+///
+/// Path separators (`/`) are preserved so that each segment of the path
+/// is encoded individually.
+///
+/// # Examples
+/// ```
+/// use imgix::encode;
+///
+/// assert_eq!(encode::path("images/my image#1.png"), "images/my%20image%231.png");
+/// ```
+pub fn path(p: &str) -> String {
+    encode_except(p, PATH_SAFE)
+}
+
+/// Encode a query-string key or value (i.e. "16:9").
+///
+/// # Examples
+/// ```
+/// use imgix::encode;
+///
+/// assert_eq!(encode::component("16:9"), "16%3A9");
+/// ```
+pub fn component(c: &str) -> String {
+    encode_except(c, COMPONENT_SAFE)
+}
+
+/// The URL-safe base64 alphabet (RFC 4648 §5).
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode the value of a base64 parameter variant (i.e. `txt64`).
+///
+/// The result uses the URL-safe alphabet and is _unpadded_, so it can be
+/// placed in a query string as-is.
+///
+/// # Examples
+/// ```
+/// use imgix::encode;
+///
+/// assert_eq!(encode::base64("Hello, World!"), "SGVsbG8sIFdvcmxkIQ");
+/// ```
+pub fn base64(v: &str) -> String {
+    let bytes = v.as_bytes();
+    let mut result = String::with_capacity((bytes.len() * 4).div_ceil(3));
+
+    // Every 3 bytes of input (24 bits) are represented by 4 characters
+    // (6 bits each); a trailing chunk of 1 or 2 bytes yields 2 or 3.
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..=chunk.len() {
+            let index = (n >> (18 - 6 * i)) & 0x3F;
+            result.push(BASE64_URL_ALPHABET[index as usize] as char);
+        }
+    }
+    result
+}
+
+/// Decode a percent-encoded path, key or value (i.e. "16%3A9").
+///
+/// # Errors
+///
+/// This function returns an `Error::ParseError` if `s` contains a malformed
+/// percent-encoded octet or if the decoded bytes are not valid UTF-8.
+pub fn decode(s: &str) -> Result<String> {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+
+    let mut i = 0usize;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let octet = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| ParseError(format!("malformed percent-encoding in `{}`", s)))?;
+            result.push(octet);
+            i += 3;
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(result).map_err(|_| ParseError(format!("`{}` is not valid UTF-8", s)))
+}
+
+/// Decode the value of a base64 parameter variant (i.e. `txt64`).
+///
+/// Both the URL-safe and the standard alphabets are accepted, with or
+/// without padding.
+///
+/// # Errors
+///
+/// This function returns an `Error::ParseError` if `v` is not valid base64
+/// or if the decoded bytes are not valid UTF-8.
+pub fn decode_base64(v: &str) -> Result<String> {
+    let error = || ParseError(format!("`{}` is not valid base64", v));
+    let v = v.trim_end_matches('=');
+
+    // A single trailing character cannot encode a whole byte.
+    if v.len() % 4 == 1 {
+        return Err(error());
+    }
+
+    let mut result = Vec::with_capacity(v.len() * 3 / 4);
+    for chunk in v.as_bytes().chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let index = match c {
+                b'+' => 62,
+                b'/' => 63,
+                _ => BASE64_URL_ALPHABET
+                    .iter()
+                    .position(|a| a == c)
+                    .ok_or_else(error)?,
+            };
+            n |= (index as u32) << (18 - 6 * i);
+        }
+
+        // Every 4 characters (24 bits) yield 3 bytes; a trailing chunk
+        // of 2 or 3 characters yields 1 or 2.
+        for i in 0..chunk.len() - 1 {
+            result.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    String::from_utf8(result).map_err(|_| ParseError(format!("`{}` is not valid UTF-8", v)))
+}
+
+/// Returns `true` if `k` is the key of a base64 parameter variant
+/// (i.e. `txt64`, `mark64`, `blend64`).
+pub fn is_base64_key(k: &str) -> bool {
+    k.ends_with("64")
+}
+
+/// Percent-encode every byte of `s` that is neither an ASCII alphanumeric
+/// nor contained in `safe`.
+fn encode_except(s: &str, safe: &[u8]) -> String {
+    let mut result = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || safe.contains(&b) {
+            result.push(b as char);
+        } else {
+            // Writing to a `String` cannot fail.
+            write!(result, "%{:02X}", b).unwrap();
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_unreserved() {
+        assert_eq!(
+            path("images/image-1_a.b~(c)!*'.png"),
+            "images/image-1_a.b~(c)!*'.png"
+        );
+    }
+
+    #[test]
+    fn test_path_reserved() {
+        assert_eq!(path("a b/c#d?e:f+g.png"), "a%20b/c%23d%3Fe%3Af%2Bg.png");
+        assert_eq!(path("a;b,c@d&e=f$g.png"), "a;b,c@d&e=f$g.png");
+        assert_eq!(path("100%.png"), "100%25.png");
+    }
+
+    #[test]
+    fn test_path_unicode() {
+        assert_eq!(
+            path("ساندویچ.jpg"),
+            "%D8%B3%D8%A7%D9%86%D8%AF%D9%88%DB%8C%DA%86.jpg"
+        );
+    }
+
+    #[test]
+    fn test_component() {
+        assert_eq!(component("4:3"), "4%3A3");
+        assert_eq!(component("Hello, World!"), "Hello%2C%20World!");
+        assert_eq!(component("a/b?c=d&e#f"), "a%2Fb%3Fc%3Dd%26e%23f");
+        assert_eq!(component("🐱"), "%F0%9F%90%B1");
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(""), "");
+        assert_eq!(base64("a"), "YQ");
+        assert_eq!(base64("ab"), "YWI");
+        assert_eq!(base64("abc"), "YWJj");
+        assert_eq!(base64("Hello 🌎 & Friends"), "SGVsbG8g8J-MjiAmIEZyaWVuZHM");
+        assert_eq!(
+            base64("https://assets.imgix.net/logo.png?w=100"),
+            "aHR0cHM6Ly9hc3NldHMuaW1naXgubmV0L2xvZ28ucG5nP3c9MTAw"
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("4%3A3").unwrap(), "4:3");
+        assert_eq!(decode("a%20b/c%23d%3Fe.png").unwrap(), "a b/c#d?e.png");
+        assert_eq!(decode("%F0%9F%90%B1").unwrap(), "🐱");
+        assert_eq!(decode("a+b").unwrap(), "a+b");
+        assert!(decode("100%").is_err());
+        assert!(decode("%zz").is_err());
+        assert!(decode("%FF").is_err());
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        for v in &["", "a", "ab", "abc", "Hello 🌎 & Friends"] {
+            assert_eq!(decode_base64(&base64(v)).unwrap(), *v);
+        }
+        assert_eq!(
+            decode_base64("SGVsbG8sIFdvcmxkIQ==").unwrap(),
+            "Hello, World!"
+        );
+        assert!(decode_base64("SGVsbG8*").is_err());
+        assert!(decode_base64("SGVsb").is_err());
+    }
+}
@@ -1,12 +1,18 @@
 // Module declarations.
+pub mod clock;
 pub mod constants;
+pub mod encode;
+pub mod metadata;
+pub mod palette;
+pub mod params;
 pub mod source_set;
 pub mod url;
 pub mod util;
 pub mod validate;
 
+pub use clock::{Clock, SystemClock};
 pub use constants::lib_version;
-pub use url::{Scheme, Url};
+pub use url::{verify, PathKind, Scheme, Signature, Url};
 /// Re-exports.
 pub use util::command_prelude;
 pub use util::errors::{Error, Result};
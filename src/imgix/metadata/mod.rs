@@ -0,0 +1,224 @@
+//! Parsing of the image metadata returned by `fm=json` requests.
+//!
+//! See `Url::metadata()` for building these requests.
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::params::Orient;
+use crate::Result;
+
+/// The color model of an image.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub enum ColorModel {
+    #[serde(rename = "RGB")]
+    Rgb,
+    #[serde(rename = "Gray")]
+    Gray,
+    #[serde(rename = "CMYK")]
+    Cmyk,
+    #[serde(rename = "Lab")]
+    Lab,
+    /// A color model not listed above.
+    #[serde(other)]
+    Other,
+}
+
+/// The metadata of an image, as returned by an `fm=json` request.
+///
+/// Only the fields needed to lay out an image are parsed; imgix omits
+/// those that do not apply to the image (i.e. `orientation` for PNGs).
+///
+/// # Examples
+/// ```
+/// use imgix::metadata::{ColorModel, ImageMetadata};
+///
+/// let json = br#"{"PixelWidth": 640, "PixelHeight": 480, "ColorModel": "RGB", "Orientation": 8}"#;
+///
+/// let metadata = ImageMetadata::from_slice(json).unwrap();
+/// assert_eq!(metadata.color_model, Some(ColorModel::Rgb));
+/// assert_eq!(metadata.oriented_dimensions(), (480, 640));
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ImageMetadata {
+    /// The stored width, in pixels, of the image.
+    pub pixel_width: u32,
+    /// The stored height, in pixels, of the image.
+    pub pixel_height: u32,
+    /// The horizontal resolution, in dots per inch.
+    #[serde(rename = "DPIWidth", default)]
+    pub dpi_width: Option<f32>,
+    /// The vertical resolution, in dots per inch.
+    #[serde(rename = "DPIHeight", default)]
+    pub dpi_height: Option<f32>,
+    /// The color model of the image.
+    #[serde(default)]
+    pub color_model: Option<ColorModel>,
+    /// The number of bits per color component.
+    #[serde(default)]
+    pub depth: Option<u32>,
+    /// The name of the embedded color profile.
+    #[serde(default)]
+    pub profile_name: Option<String>,
+    /// Whether the image has an alpha channel.
+    #[serde(default)]
+    pub has_alpha: Option<bool>,
+    /// The EXIF orientation of the image, if it is known.
+    #[serde(default, deserialize_with = "orientation")]
+    pub orientation: Option<Orient>,
+    /// The MIME type of the image (i.e. "image/jpeg").
+    #[serde(rename = "Content-Type", default)]
+    pub content_type: Option<String>,
+    /// The size, in bytes, of the image.
+    #[serde(
+        rename = "Content-Length",
+        default,
+        deserialize_with = "content_length"
+    )]
+    pub content_length: Option<u64>,
+}
+
+impl ImageMetadata {
+    /// Parse an `ImageMetadata` from the bytes of a JSON document.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParseError` if `bytes` is not a
+    /// valid metadata document.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// The width and height, in pixels, of the image as it is displayed,
+    /// that is after its EXIF `orientation` has been applied.
+    pub fn oriented_dimensions(&self) -> (u32, u32) {
+        match self.orientation {
+            Some(Orient::LeftTop)
+            | Some(Orient::RightTop)
+            | Some(Orient::RightBottom)
+            | Some(Orient::LeftBottom)
+            | Some(Orient::Rotate90)
+            | Some(Orient::Rotate270) => (self.pixel_height, self.pixel_width),
+            _ => (self.pixel_width, self.pixel_height),
+        }
+    }
+}
+
+/// Deserialize an EXIF orientation (i.e. 6) into an `Orient`.
+///
+/// Only the EXIF orientations 1 through 8 are accepted. Other values
+/// (i.e. 0, which some encoders write, or `null`) are unknown and become
+/// `None`, rather than failing to parse the rest of the metadata.
+fn orientation<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Orient>, D::Error> {
+    let value = match Option::<i64>::deserialize(deserializer)? {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    match u16::try_from(value) {
+        Ok(value @ 1..=8) => Ok(Orient::try_from(value).ok()),
+        _ => Ok(None),
+    }
+}
+
+/// Deserialize a content length, which imgix returns as a string
+/// (i.e. "48211"), into a number.
+fn content_length<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<u64>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    fn fixture(name: &str) -> Vec<u8> {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_metadata_jpeg() {
+        let metadata = ImageMetadata::from_slice(&fixture("metadata_jpeg.json")).unwrap();
+
+        let right = ImageMetadata {
+            pixel_width: 4032,
+            pixel_height: 3024,
+            dpi_width: Some(72.0),
+            dpi_height: Some(72.0),
+            color_model: Some(ColorModel::Rgb),
+            depth: Some(8),
+            profile_name: Some("sRGB IEC61966-2.1".to_owned()),
+            has_alpha: None,
+            orientation: Some(Orient::RightTop),
+            content_type: Some("image/jpeg".to_owned()),
+            content_length: Some(2_315_124),
+        };
+        assert_eq!(metadata, right);
+        assert_eq!(metadata.oriented_dimensions(), (3024, 4032));
+    }
+
+    #[test]
+    fn test_metadata_png() {
+        let metadata = ImageMetadata::from_slice(&fixture("metadata_png.json")).unwrap();
+
+        let right = ImageMetadata {
+            pixel_width: 1024,
+            pixel_height: 512,
+            dpi_width: None,
+            dpi_height: None,
+            color_model: Some(ColorModel::Gray),
+            depth: Some(16),
+            profile_name: None,
+            has_alpha: Some(true),
+            orientation: None,
+            content_type: Some("image/png".to_owned()),
+            content_length: Some(48_211),
+        };
+        assert_eq!(metadata, right);
+        assert_eq!(metadata.oriented_dimensions(), (1024, 512));
+    }
+
+    #[test]
+    fn test_metadata_unknown_color_model() {
+        let json = br#"{"PixelWidth": 1, "PixelHeight": 1, "ColorModel": "YCbCr"}"#;
+        let metadata = ImageMetadata::from_slice(json).unwrap();
+        assert_eq!(metadata.color_model, Some(ColorModel::Other));
+    }
+
+    #[test]
+    fn test_metadata_unknown_orientation() {
+        for orientation in ["0", "9", "90", "270", "-1", "null"].iter() {
+            let json = format!(
+                r#"{{"PixelWidth": 640, "PixelHeight": 480, "Orientation": {}}}"#,
+                orientation
+            );
+            let metadata = ImageMetadata::from_slice(json.as_bytes()).unwrap();
+            assert_eq!(metadata.orientation, None);
+            assert_eq!(metadata.oriented_dimensions(), (640, 480));
+        }
+    }
+
+    #[test]
+    fn test_metadata_invalid() {
+        let invalid: [&[u8]; 4] = [
+            b"",
+            br#"{"PixelWidth": 640}"#,
+            br#"{"PixelWidth": 640, "PixelHeight": 480, "Orientation": "up"}"#,
+            br#"{"PixelWidth": 640, "PixelHeight": 480, "Content-Length": "many"}"#,
+        ];
+
+        for bytes in invalid.iter() {
+            assert!(matches!(
+                ImageMetadata::from_slice(bytes),
+                Err(Error::ParseError(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Parsing of the color palettes returned by `palette=json` requests.
+//!
+//! See `Url::palette()` for building these requests.
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::params::Color;
+use crate::Result;
+
+/// The color palette of an image, as returned by a `palette=json` request.
+///
+/// # Examples
+/// ```
+/// use imgix::palette::Palette;
+///
+/// let json = br##"{
+///     "colors": [{"red": 1.0, "hex": "#ff0000", "blue": 0.0, "green": 0.0}],
+///     "average_luminance": 0.2126,
+///     "dominant_colors": {}
+/// }"##;
+///
+/// let palette = Palette::from_slice(json).unwrap();
+/// assert_eq!(palette.colors[0].hex.to_string(), "ff0000");
+/// assert!(palette.dominant_colors.vibrant.is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Palette {
+    /// The colors of the image, up to the number of `colors` requested.
+    pub colors: Vec<PaletteColor>,
+    /// The average luminance of the image, from 0.0 to 1.0.
+    pub average_luminance: f32,
+    /// The dominant colors of the image.
+    pub dominant_colors: DominantColors,
+}
+
+/// A color of a `Palette`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PaletteColor {
+    /// The red component, from 0.0 to 1.0.
+    pub red: f32,
+    /// The green component, from 0.0 to 1.0.
+    pub green: f32,
+    /// The blue component, from 0.0 to 1.0.
+    pub blue: f32,
+    /// The color, as it would be passed to a color parameter.
+    #[serde(deserialize_with = "hex")]
+    pub hex: Color,
+}
+
+/// The dominant colors of a `Palette`. imgix omits those it does not find.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct DominantColors {
+    pub vibrant: Option<PaletteColor>,
+    pub vibrant_light: Option<PaletteColor>,
+    pub vibrant_dark: Option<PaletteColor>,
+    pub muted: Option<PaletteColor>,
+    pub muted_light: Option<PaletteColor>,
+    pub muted_dark: Option<PaletteColor>,
+}
+
+impl Palette {
+    /// Parse a `Palette` from the bytes of a JSON document.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParseError` if `bytes` is not a
+    /// valid palette document.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// Parse a `Palette` from the JSON document at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::Io` if `path` cannot be read and
+    /// an `Error::ParseError` if it is not a valid palette document.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Palette::from_slice(&fs::read(path)?)
+    }
+}
+
+/// Deserialize a hex color (i.e. "#f9f1e6") into a `Color`.
+fn hex<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Color, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    fn fixture() -> String {
+        format!("{}/tests/fixtures/palette.json", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    #[test]
+    fn test_palette_from_file() {
+        let palette = Palette::from_file(fixture()).unwrap();
+
+        let hexes: Vec<String> = palette.colors.iter().map(|c| c.hex.to_string()).collect();
+        assert_eq!(hexes, ["f9f1e6", "d8a95b", "66331a"]);
+        assert_eq!(palette.colors[2].red, 0.4);
+        assert_eq!(palette.average_luminance, 0.627431);
+
+        let dominant = &palette.dominant_colors;
+        assert_eq!(dominant.vibrant, Some(palette.colors[1].clone()));
+        assert_eq!(dominant.muted_light, Some(palette.colors[0].clone()));
+        assert_eq!(dominant.muted_dark, Some(palette.colors[2].clone()));
+        assert_eq!(dominant.vibrant_light, None);
+        assert_eq!(dominant.vibrant_dark, None);
+        assert_eq!(dominant.muted, None);
+    }
+
+    #[test]
+    fn test_palette_from_slice() {
+        let bytes = std::fs::read(fixture()).unwrap();
+        assert_eq!(
+            Palette::from_slice(&bytes).unwrap(),
+            Palette::from_file(fixture()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_palette_invalid() {
+        let invalid: [&[u8]; 3] = [
+            b"",
+            b"{\"colors\": []}",
+            br##"{"colors": [{"red": 0, "green": 0, "blue": 0, "hex": "#zz"}],
+                "average_luminance": 0, "dominant_colors": {}}"##,
+        ];
+
+        for bytes in invalid.iter() {
+            assert!(matches!(
+                Palette::from_slice(bytes),
+                Err(Error::ParseError(_))
+            ));
+        }
+
+        assert!(matches!(
+            Palette::from_file("does/not/exist.json"),
+            Err(Error::Io(_))
+        ));
+    }
+}
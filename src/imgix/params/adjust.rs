@@ -0,0 +1,164 @@
+//! Adjustment parameters (i.e. `bri`, `con`, `exp`, `gam`, `high`, `hue`,
+//! `sat`, `shad`, `sharp`, `usm`, `vib`).
+//!
+//! imgix silently clamps out-of-range adjustments, so these building
+//! functions reject them instead.
+use crate::{validate, Result, Url};
+
+impl Url {
+    ranged_param!(
+        /// Set the brightness, from -100 to 100 (i.e. `bri`).
+        bri,
+        try_bri,
+        "bri",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the contrast, from -100 to 100 (i.e. `con`).
+        con,
+        try_con,
+        "con",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the exposure, from -100 to 100 (i.e. `exp`).
+        exp,
+        try_exp,
+        "exp",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the gamma, from -100 to 100 (i.e. `gam`).
+        gam,
+        try_gam,
+        "gam",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the highlights, from -100 to 0 (i.e. `high`).
+        high,
+        try_high,
+        "high",
+        -100..=0,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the hue shift, in degrees from 0 to 359 (i.e. `hue`).
+        hue,
+        try_hue,
+        "hue",
+        0..=359,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the saturation, from -100 to 100 (i.e. `sat`).
+        sat,
+        try_sat,
+        "sat",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the shadows, from 0 to 100 (i.e. `shad`).
+        shad,
+        try_shad,
+        "shad",
+        0..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the sharpening, from 0 to 100 (i.e. `sharp`).
+        sharp,
+        try_sharp,
+        "sharp",
+        0..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the unsharp mask, from -100 to 100 (i.e. `usm`).
+        usm,
+        try_usm,
+        "usm",
+        -100..=100,
+        i32
+    );
+
+    ranged_param!(
+        /// Set the vibrance, from -100 to 100 (i.e. `vib`).
+        vib,
+        try_vib,
+        "vib",
+        -100..=100,
+        i32
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_adjustments() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .bri(-100)
+            .con(100)
+            .exp(10)
+            .gam(-10)
+            .high(-50)
+            .hue(359)
+            .sat(0)
+            .shad(100)
+            .sharp(0)
+            .usm(20)
+            .vib(-20);
+
+        let right = "https://test.domain.com/image.png?bri=-100&con=100&exp=10&gam=-10&high=-50&hue=359&sat=0&shad=100&sharp=0&usm=20&vib=-20";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_adjustments_out_of_range() {
+        let results = [
+            Url::new(DOMAIN).try_bri(101),
+            Url::new(DOMAIN).try_con(-101),
+            Url::new(DOMAIN).try_exp(101),
+            Url::new(DOMAIN).try_gam(-101),
+            Url::new(DOMAIN).try_high(1),
+            Url::new(DOMAIN).try_hue(-1),
+            Url::new(DOMAIN).try_hue(360),
+            Url::new(DOMAIN).try_sat(101),
+            Url::new(DOMAIN).try_shad(-1),
+            Url::new(DOMAIN).try_sharp(101),
+            Url::new(DOMAIN).try_usm(-101),
+            Url::new(DOMAIN).try_vib(101),
+        ];
+
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_adjustment_panics() {
+        let _ = Url::new(DOMAIN).bri(200);
+    }
+}
@@ -0,0 +1,81 @@
+//! Alignments used by alignment parameters (i.e. `txt-align`).
+use std::fmt::{self, Display};
+
+use crate::{Error, Result};
+
+/// A vertical or horizontal alignment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Top,
+    Middle,
+    Bottom,
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    fn is_vertical(self) -> bool {
+        matches!(self, Align::Top | Align::Middle | Align::Bottom)
+    }
+}
+
+impl Display for Align {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Align::Top => "top",
+            Align::Middle => "middle",
+            Align::Bottom => "bottom",
+            Align::Left => "left",
+            Align::Center => "center",
+            Align::Right => "right",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// Join the alignments `align` of the parameter `k` (i.e. "bottom,right").
+///
+/// # Errors
+///
+/// This function returns an `Error::ParamError` if `align` is empty or if
+/// it contains more than one vertical or more than one horizontal alignment.
+pub(crate) fn join(k: &str, align: &[Align]) -> Result<String> {
+    let vertical = align.iter().filter(|a| a.is_vertical()).count();
+    let horizontal = align.len() - vertical;
+
+    if align.is_empty() || vertical > 1 || horizontal > 1 {
+        return Err(Error::ParamError(format!(
+            "`{}` takes at most one vertical and one horizontal alignment, found {:?}",
+            k, align
+        )));
+    }
+
+    Ok(align
+        .iter()
+        .map(Align::to_string)
+        .collect::<Vec<String>>()
+        .join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_join() {
+        assert_eq!(join("k", &[Align::Top]).unwrap(), "top");
+        assert_eq!(join("k", &[Align::Right]).unwrap(), "right");
+        assert_eq!(
+            join("k", &[Align::Bottom, Align::Center]).unwrap(),
+            "bottom,center"
+        );
+    }
+
+    #[test]
+    fn test_join_invalid() {
+        assert!(join("k", &[]).is_err());
+        assert!(join("k", &[Align::Top, Align::Bottom]).is_err());
+        assert!(join("k", &[Align::Left, Align::Middle, Align::Right]).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Multi-page and animation parameters (i.e. `page`, `frame`, `fps`,
+//! `loop`, `reverse`).
+use super::value;
+use crate::{validate, Error, Result, Url};
+
+/// The output formats (i.e. values of `fm`) that can be animated.
+const ANIMATED_FORMATS: &[&str] = &["gif", "mp4", "webm", "webp"];
+
+/// The parameters that only apply to animated output.
+const ANIMATION_PARAMS: &[&str] = &["fps", "loop", "reverse"];
+
+impl Url {
+    /// Set the page, counting from 1, of a multi-page source (i.e. a PDF)
+    /// to render (i.e. `page`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `page` is 0.
+    pub fn page(self, page: u32) -> Self {
+        match self.try_page(page) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the page, counting from 1, of a multi-page source to render
+    /// (i.e. `page`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `page` is 0.
+    pub fn try_page(mut self, page: u32) -> Result<Self> {
+        validate::positive("page", page)?;
+        self.set_param("page", page.to_string());
+        Ok(self)
+    }
+
+    /// Set the frame, counting from 1, of an animated source to render
+    /// as a still image (i.e. `frame`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `frame` is 0.
+    pub fn frame(self, frame: u32) -> Self {
+        match self.try_frame(frame) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the frame, counting from 1, of an animated source to render
+    /// as a still image (i.e. `frame`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `frame` is 0.
+    pub fn try_frame(mut self, frame: u32) -> Result<Self> {
+        validate::positive("frame", frame)?;
+        self.set_param("frame", frame.to_string());
+        Ok(self)
+    }
+
+    /// Set the frame rate of animated output (i.e. `fps`).
+    ///
+    /// Animation parameters are only valid if the output format, `fm`, is
+    /// unset or can be animated (i.e. `gif`, `mp4`, `webm`, `webp`), see
+    /// `Url::validate()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Format;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("animation.gif")
+    ///     .fm(Format::Mp4)
+    ///     .fps(24)
+    ///     .loops(0);
+    /// assert_eq!(
+    ///     url.join(),
+    ///     "https://example.domain.net/animation.gif?fm=mp4&fps=24&loop=0"
+    /// );
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("animation.gif")
+    ///     .fm(Format::Jpg)
+    ///     .fps(24);
+    /// assert!(url.validate().is_err());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `fps` is 0.
+    pub fn fps(self, fps: u32) -> Self {
+        match self.try_fps(fps) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the frame rate of animated output (i.e. `fps`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `fps` is 0.
+    pub fn try_fps(mut self, fps: u32) -> Result<Self> {
+        validate::positive("fps", fps)?;
+        self.set_param("fps", fps.to_string());
+        Ok(self)
+    }
+
+    /// Set how many times animated output loops, where 0 loops forever
+    /// (i.e. `loop`).
+    pub fn loops(mut self, loops: u32) -> Self {
+        self.set_param("loop", loops.to_string());
+        self
+    }
+
+    /// Set whether animated output plays in reverse (i.e. `reverse`).
+    pub fn reverse(mut self, state: bool) -> Self {
+        self.set_param("reverse", state.to_string());
+        self
+    }
+}
+
+/// Check that animation parameters are not combined with an output
+/// format that cannot be animated, and that `page` and `frame` count
+/// from 1.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    for k in ["page", "frame"].iter() {
+        if value(params, k) == Some("0") {
+            return Err(Error::ParamError(format!(
+                "cannot use a `{}` of 0, it counts from 1",
+                k
+            )));
+        }
+    }
+
+    let fm = match value(params, "fm") {
+        Some(fm) if !ANIMATED_FORMATS.contains(&fm) => fm,
+        _ => return Ok(()),
+    };
+
+    match ANIMATION_PARAMS.iter().find(|k| value(params, k).is_some()) {
+        Some(k) => Err(Error::ParamError(format!(
+            "cannot use a `{}` with the static format `fm={}`",
+            k, fm
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::params::Format;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "brochure.pdf";
+
+    #[test]
+    fn test_page_and_frame() {
+        let url = Url::new(DOMAIN).path(PATH).page(2).fm(Format::Png);
+        assert_eq!(
+            url.join(),
+            "https://test.domain.com/brochure.pdf?page=2&fm=png"
+        );
+
+        // A single frame is a still image.
+        let url = Url::new(DOMAIN).path("a.gif").frame(1).fm(Format::Jpg);
+        assert_eq!(url.join(), "https://test.domain.com/a.gif?frame=1&fm=jpg");
+
+        let results = [
+            Url::new(DOMAIN).try_page(0),
+            Url::new(DOMAIN).try_frame(0),
+            Url::new(DOMAIN).try_fps(0),
+        ];
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+
+    #[test]
+    fn test_animation_params() {
+        for fm in [Format::Gif, Format::Mp4, Format::Webm, Format::Webp].iter() {
+            let url = Url::new(DOMAIN)
+                .path("a.gif")
+                .fm(*fm)
+                .fps(12)
+                .loops(3)
+                .reverse(true);
+            assert!(url.validate().is_ok());
+        }
+
+        // Without `fm`, the output format is that of the source.
+        let url = Url::new(DOMAIN).path("a.gif").reverse(false);
+        assert_eq!(url.join(), "https://test.domain.com/a.gif?reverse=false");
+    }
+
+    #[test]
+    fn test_animation_params_with_static_format() {
+        let invalid = [
+            Url::new(DOMAIN).path("a.gif").fm(Format::Jpg).fps(12),
+            Url::new(DOMAIN).path("a.gif").loops(0).fm(Format::Png),
+            Url::new(DOMAIN)
+                .path("a.gif")
+                .param("reverse", "true")
+                .param("fm", "avif"),
+            Url::new(DOMAIN).path(PATH).param("page", "0"),
+        ];
+
+        for url in invalid.iter() {
+            assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Blend and mask parameters (i.e. `blend`, `blend-mode`, `blend-alpha`,
+//! `blend-align`, `blend-size`, `mask`, `corner-radius`).
+use std::fmt::{self, Display};
+
+use super::{align, value, Align, Color};
+use crate::{validate, Error, Result, Url};
+
+/// How the `blend` is composited onto the image (i.e. the `blend-mode`
+/// parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    Burn,
+    Color,
+    Darken,
+    Difference,
+    Dodge,
+    Exclusion,
+    HardLight,
+    Hue,
+    Lighten,
+    Luminosity,
+    Multiply,
+    Normal,
+    Overlay,
+    Saturation,
+    Screen,
+    SoftLight,
+}
+
+impl Display for BlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            BlendMode::Burn => "burn",
+            BlendMode::Color => "color",
+            BlendMode::Darken => "darken",
+            BlendMode::Difference => "difference",
+            BlendMode::Dodge => "dodge",
+            BlendMode::Exclusion => "exclusion",
+            BlendMode::HardLight => "hardlight",
+            BlendMode::Hue => "hue",
+            BlendMode::Lighten => "lighten",
+            BlendMode::Luminosity => "luminosity",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Normal => "normal",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Saturation => "saturation",
+            BlendMode::Screen => "screen",
+            BlendMode::SoftLight => "softlight",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// How the `blend` image is sized (i.e. the `blend-size` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendSize {
+    /// Resize the blend image to the size of the image.
+    Inherit,
+}
+
+impl Display for BlendSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            BlendSize::Inherit => "inherit",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// The shape the image is masked to (i.e. the `mask` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mask {
+    /// An ellipse inscribed in the image.
+    Ellipse,
+    /// The image with rounded corners, see `Url::corner_radius()`.
+    Corners,
+}
+
+impl Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Mask::Ellipse => "ellipse",
+            Mask::Corners => "corners",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Url {
+    /// Blend a solid `color` onto the image (i.e. `blend`), replacing any
+    /// blend set previously.
+    ///
+    /// Like a `Watermark`, the blend is attached to the `Url` and follows
+    /// its own parameters in the query string.
+    pub fn blend_color(mut self, color: Color) -> Self {
+        self.set_blend(color.to_string());
+        self
+    }
+
+    /// Blend the image at the imgix `Url` `image` onto the image
+    /// (i.e. `blend`), replacing any blend set previously. `image` is
+    /// joined, and signed with its own `token`, when it is set. The joined
+    /// URL is always percent-encoded as a single parameter value, even if
+    /// this `Url` has encoding turned off.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `image` cannot be joined, see `Url::join()`.
+    pub fn blend_image(self, image: &Url) -> Self {
+        match self.try_blend_image(image) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Blend the image at the imgix `Url` `image` onto the image
+    /// (i.e. `blend`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::JoinError` if `image` cannot be
+    /// joined, see `Url::try_join()`.
+    pub fn try_blend_image(mut self, image: &Url) -> Result<Self> {
+        self.set_blend(image.try_join()?);
+        Ok(self)
+    }
+
+    /// Set how the blend is composited onto the image (i.e. `blend-mode`).
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.set_param("blend-mode", mode.to_string());
+        self
+    }
+
+    /// Set the opacity of the blend, from 0 to 100 (i.e. `blend-alpha`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `alpha` is greater than 100.
+    pub fn blend_alpha(self, alpha: u32) -> Self {
+        match self.try_blend_alpha(alpha) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the opacity of the blend, from 0 to 100 (i.e. `blend-alpha`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `alpha` is greater
+    /// than 100.
+    pub fn try_blend_alpha(mut self, alpha: u32) -> Result<Self> {
+        validate::in_range("blend-alpha", alpha, 0..=100)?;
+        self.set_param("blend-alpha", alpha.to_string());
+        Ok(self)
+    }
+
+    /// Set the alignment of the blend image (i.e. `blend-align`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `align` is empty or if it contains more than
+    /// one vertical or more than one horizontal alignment.
+    pub fn blend_align(self, align: &[Align]) -> Self {
+        match self.try_blend_align(align) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the alignment of the blend image (i.e. `blend-align`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `align` is empty or if
+    /// it contains more than one vertical or more than one horizontal
+    /// alignment.
+    pub fn try_blend_align(mut self, align: &[Align]) -> Result<Self> {
+        self.set_param("blend-align", align::join("blend-align", align)?);
+        Ok(self)
+    }
+
+    /// Set how the blend image is sized (i.e. `blend-size`).
+    pub fn blend_size(mut self, size: BlendSize) -> Self {
+        self.set_param("blend-size", size.to_string());
+        self
+    }
+
+    /// Set the shape the image is masked to (i.e. `mask`).
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.set_param("mask", mask.to_string());
+        self
+    }
+
+    /// Set the radius, in pixels, of every corner of a `Mask::Corners`
+    /// mask (i.e. `corner-radius`).
+    ///
+    /// A `corner-radius` is only valid if the `mask` is `Mask::Corners`,
+    /// see `Url::validate()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Mask;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .mask(Mask::Corners)
+    ///     .corner_radius(16);
+    /// assert_eq!(
+    ///     url.join(),
+    ///     "https://example.domain.net/image.png?mask=corners&corner-radius=16"
+    /// );
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .corner_radius(16);
+    /// assert!(url.validate().is_err());
+    /// ```
+    pub fn corner_radius(mut self, radius: u32) -> Self {
+        self.set_param("corner-radius", radius.to_string());
+        self
+    }
+
+    /// Set the radius, in pixels, of each corner of a `Mask::Corners`
+    /// mask, clockwise from the top-left corner (i.e. `corner-radius`).
+    pub fn corner_radii(mut self, tl: u32, tr: u32, br: u32, bl: u32) -> Self {
+        let radii = format!("{},{},{},{}", tl, tr, br, bl);
+        self.set_param("corner-radius", radii);
+        self
+    }
+}
+
+/// Check that the `corner-radius` parameter is only used along with
+/// `mask=corners`.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    if value(params, "corner-radius").is_some() && value(params, "mask") != Some("corners") {
+        return Err(Error::ParamError(
+            "cannot use a `corner-radius` without `mask=corners`".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_blend_color() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .blend_color(Color::argb(128, 255, 0, 0))
+            .blend_mode(BlendMode::Multiply)
+            .blend_alpha(60)
+            .blend_align(&[Align::Top])
+            .blend_size(BlendSize::Inherit);
+
+        let right = "https://test.domain.com/image.png?blend-mode=multiply&blend-alpha=60&blend-align=top&blend-size=inherit&blend=80ff0000";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_blend_image() {
+        let texture = Url::new("assets.domain.com").path("paper.png").w(320);
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .blend_image(&texture)
+            .blend_mode(BlendMode::SoftLight);
+
+        let right = "https://test.domain.com/image.png?blend-mode=softlight&blend=https%3A%2F%2Fassets.domain.com%2Fpaper.png%3Fw%3D320";
+        assert_eq!(url.join(), right);
+
+        assert!(matches!(
+            Url::new(DOMAIN).try_blend_image(&Url::new(DOMAIN)),
+            Err(Error::JoinError(_))
+        ));
+    }
+
+    #[test]
+    fn test_blend_image_encode_disabled() {
+        let texture = Url::new("a.net").path("paper.png").w(64).h(10);
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .blend_image(&texture)
+            .param("ar", "16%3A9")
+            .encode(false);
+
+        let right = "https://test.domain.com/image.png\
+            ?ar=16%3A9&blend=https%3A%2F%2Fa.net%2Fpaper.png%3Fw%3D64%26h%3D10";
+        assert_eq!(url.join(), right);
+
+        // A color replaces the image.
+        let url = url.blend_color(Color::rgb(255, 0, 0));
+        let right = "https://test.domain.com/image.png?ar=16%3A9&blend=ff0000";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_blend_replaces_params() {
+        let url: Url = "https://test.domain.com/image.png?blend=000000&w=10"
+            .parse()
+            .unwrap();
+        let url = url.blend_color(Color::rgb(255, 0, 0));
+        let right = "https://test.domain.com/image.png?w=10&blend=ff0000";
+        assert_eq!(url.join(), right);
+
+        assert!(matches!(
+            url.try_param("blend", "00ff00"),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_blend_invalid() {
+        assert!(matches!(
+            Url::new(DOMAIN).try_blend_alpha(101),
+            Err(Error::RangeError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN).try_blend_align(&[Align::Left, Align::Right]),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_mask() {
+        let url = Url::new(DOMAIN).path(PATH).mask(Mask::Ellipse);
+        assert_eq!(url.join(), "https://test.domain.com/image.png?mask=ellipse");
+
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .corner_radii(0, 8, 16, 32)
+            .mask(Mask::Corners);
+        let right = "https://test.domain.com/image.png?corner-radius=0%2C8%2C16%2C32&mask=corners";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_corner_radius_without_corners_mask() {
+        let url = Url::new(DOMAIN).path(PATH).corner_radius(8);
+        assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .mask(Mask::Ellipse)
+            .corner_radius(8);
+        assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+
+        // Untyped parameters are checked too, but are still joined as-is.
+        let url = Url::new(DOMAIN).path(PATH).param("corner-radius", "8");
+        assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+        let right = "https://test.domain.com/image.png?corner-radius=8";
+        assert_eq!(url.join(), right);
+    }
+}
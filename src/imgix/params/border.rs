@@ -0,0 +1,144 @@
+//! Border and padding parameters (i.e. `border`, `border-radius`,
+//! `border-radius-inner`, `border-top`, `border-bottom`, `border-left`,
+//! `border-right`, `pad`, `pad-top`, `pad-bottom`, `pad-left`,
+//! `pad-right`, `bg`).
+use super::Color;
+use crate::Url;
+
+impl Url {
+    /// Set a border of `size` pixels and `color` around the image
+    /// (i.e. `border=size,color`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Color;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .border(4, "#000".parse().unwrap())
+    ///     .border_radius(8)
+    ///     .bg(Color::rgb(255, 255, 255));
+    ///
+    /// let right = "https://example.domain.net/image.png?border=4%2C000&border-radius=8&bg=ffffff";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    pub fn border(mut self, size: u32, color: Color) -> Self {
+        self.set_param("border", format!("{},{}", size, color));
+        self
+    }
+
+    /// Set the radius, in pixels, of the outer corners of the border
+    /// (i.e. `border-radius`).
+    pub fn border_radius(mut self, radius: u32) -> Self {
+        self.set_param("border-radius", radius.to_string());
+        self
+    }
+
+    /// Set the radius, in pixels, of the inner corners of the border
+    /// (i.e. `border-radius-inner`).
+    pub fn border_radius_inner(mut self, radius: u32) -> Self {
+        self.set_param("border-radius-inner", radius.to_string());
+        self
+    }
+
+    /// Set the width, in pixels, of the top border (i.e. `border-top`).
+    pub fn border_top(mut self, size: u32) -> Self {
+        self.set_param("border-top", size.to_string());
+        self
+    }
+
+    /// Set the width, in pixels, of the bottom border (i.e. `border-bottom`).
+    pub fn border_bottom(mut self, size: u32) -> Self {
+        self.set_param("border-bottom", size.to_string());
+        self
+    }
+
+    /// Set the width, in pixels, of the left border (i.e. `border-left`).
+    pub fn border_left(mut self, size: u32) -> Self {
+        self.set_param("border-left", size.to_string());
+        self
+    }
+
+    /// Set the width, in pixels, of the right border (i.e. `border-right`).
+    pub fn border_right(mut self, size: u32) -> Self {
+        self.set_param("border-right", size.to_string());
+        self
+    }
+
+    /// Set the padding, in pixels, around the image (i.e. `pad`).
+    pub fn pad(mut self, pad: u32) -> Self {
+        self.set_param("pad", pad.to_string());
+        self
+    }
+
+    /// Set the padding, in pixels, above the image (i.e. `pad-top`).
+    pub fn pad_top(mut self, pad: u32) -> Self {
+        self.set_param("pad-top", pad.to_string());
+        self
+    }
+
+    /// Set the padding, in pixels, below the image (i.e. `pad-bottom`).
+    pub fn pad_bottom(mut self, pad: u32) -> Self {
+        self.set_param("pad-bottom", pad.to_string());
+        self
+    }
+
+    /// Set the padding, in pixels, left of the image (i.e. `pad-left`).
+    pub fn pad_left(mut self, pad: u32) -> Self {
+        self.set_param("pad-left", pad.to_string());
+        self
+    }
+
+    /// Set the padding, in pixels, right of the image (i.e. `pad-right`).
+    pub fn pad_right(mut self, pad: u32) -> Self {
+        self.set_param("pad-right", pad.to_string());
+        self
+    }
+
+    /// Set the background color of transparent images and padding
+    /// (i.e. `bg`).
+    pub fn bg(mut self, color: Color) -> Self {
+        self.set_param("bg", color.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_border_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .border(10, Color::argb(128, 0, 0, 255))
+            .border_radius(20)
+            .border_radius_inner(10)
+            .border_top(1)
+            .border_bottom(2)
+            .border_left(3)
+            .border_right(4);
+
+        let right = "https://test.domain.com/image.png?border=10%2C800000ff&border-radius=20&border-radius-inner=10&border-top=1&border-bottom=2&border-left=3&border-right=4";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_pad_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .pad(10)
+            .pad_top(1)
+            .pad_bottom(2)
+            .pad_left(3)
+            .pad_right(4)
+            .bg("White".parse().unwrap());
+
+        let right = "https://test.domain.com/image.png?pad=10&pad-top=1&pad-bottom=2&pad-left=3&pad-right=4&bg=white";
+        assert_eq!(url.join(), right);
+    }
+}
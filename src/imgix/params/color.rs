@@ -0,0 +1,298 @@
+//! Colors used by color parameters (i.e. `bg`, `txt-color`).
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// The CSS named colors, sorted so they can be binary searched.
+const NAMED_COLORS: &[&str] = &[
+    "aliceblue",
+    "antiquewhite",
+    "aqua",
+    "aquamarine",
+    "azure",
+    "beige",
+    "bisque",
+    "black",
+    "blanchedalmond",
+    "blue",
+    "blueviolet",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornflowerblue",
+    "cornsilk",
+    "crimson",
+    "cyan",
+    "darkblue",
+    "darkcyan",
+    "darkgoldenrod",
+    "darkgray",
+    "darkgreen",
+    "darkgrey",
+    "darkkhaki",
+    "darkmagenta",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkred",
+    "darksalmon",
+    "darkseagreen",
+    "darkslateblue",
+    "darkslategray",
+    "darkslategrey",
+    "darkturquoise",
+    "darkviolet",
+    "deeppink",
+    "deepskyblue",
+    "dimgray",
+    "dimgrey",
+    "dodgerblue",
+    "firebrick",
+    "floralwhite",
+    "forestgreen",
+    "fuchsia",
+    "gainsboro",
+    "ghostwhite",
+    "gold",
+    "goldenrod",
+    "gray",
+    "green",
+    "greenyellow",
+    "grey",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "indigo",
+    "ivory",
+    "khaki",
+    "lavender",
+    "lavenderblush",
+    "lawngreen",
+    "lemonchiffon",
+    "lightblue",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrodyellow",
+    "lightgray",
+    "lightgreen",
+    "lightgrey",
+    "lightpink",
+    "lightsalmon",
+    "lightseagreen",
+    "lightskyblue",
+    "lightslategray",
+    "lightslategrey",
+    "lightsteelblue",
+    "lightyellow",
+    "lime",
+    "limegreen",
+    "linen",
+    "magenta",
+    "maroon",
+    "mediumaquamarine",
+    "mediumblue",
+    "mediumorchid",
+    "mediumpurple",
+    "mediumseagreen",
+    "mediumslateblue",
+    "mediumspringgreen",
+    "mediumturquoise",
+    "mediumvioletred",
+    "midnightblue",
+    "mintcream",
+    "mistyrose",
+    "moccasin",
+    "navajowhite",
+    "navy",
+    "oldlace",
+    "olive",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegoldenrod",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "papayawhip",
+    "peachpuff",
+    "peru",
+    "pink",
+    "plum",
+    "powderblue",
+    "purple",
+    "rebeccapurple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "saddlebrown",
+    "salmon",
+    "sandybrown",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "silver",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "slategrey",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "teal",
+    "thistle",
+    "tomato",
+    "transparent",
+    "turquoise",
+    "violet",
+    "wheat",
+    "white",
+    "whitesmoke",
+    "yellow",
+    "yellowgreen",
+];
+
+/// A color, as imgix expects it (i.e. `fff`, `80ff0000`, `red`).
+///
+/// imgix colors are hexadecimal strings of 3 (RGB), 4 (ARGB), 6 (RRGGBB)
+/// or 8 (AARRGGBB) digits, or CSS named colors. A `Color` can only be
+/// constructed from a valid color, so color parameters do not need
+/// further validation.
+///
+/// # Examples
+/// ```
+/// use imgix::params::Color;
+///
+/// let red: Color = "#FF0000".parse().unwrap();
+/// assert_eq!(red.to_string(), "ff0000");
+/// assert_eq!(Color::rgb(255, 0, 0), red);
+///
+/// assert!("#ff00".parse::<Color>().is_ok());
+/// assert!("#ff00000".parse::<Color>().is_err());
+///
+/// let navy: Color = "Navy".parse().unwrap();
+/// assert_eq!(navy.to_string(), "navy");
+/// assert!("bleu".parse::<Color>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Color(String);
+
+impl Color {
+    /// Construct an opaque `Color` from its red, green and blue components.
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color(format!("{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    /// Construct a `Color` from its alpha, red, green and blue components.
+    pub fn argb(a: u8, r: u8, g: u8, b: u8) -> Self {
+        Color(format!("{:02x}{:02x}{:02x}{:02x}", a, r, g, b))
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parse a hexadecimal color, with or without a leading `#`, or a
+    /// CSS named color, in any case.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParamError` if `s` is neither made
+    /// up of 3, 4, 6 or 8 hexadecimal digits nor a CSS named color.
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.to_ascii_lowercase();
+        if NAMED_COLORS.binary_search(&name.as_str()).is_ok() {
+            return Ok(Color(name));
+        }
+
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let is_hex = hex.chars().all(|c| c.is_ascii_hexdigit());
+
+        match hex.len() {
+            3 | 4 | 6 | 8 if is_hex => Ok(Color(hex.to_ascii_lowercase())),
+            _ => Err(Error::ParamError(format!(
+                "`{}` is not a 3, 4, 6 or 8 digit hex color or a named color",
+                s
+            ))),
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        let colors = [
+            ("fff", "fff"),
+            ("#FFF", "fff"),
+            ("8fff", "8fff"),
+            ("#00Ff00", "00ff00"),
+            ("80ff0000", "80ff0000"),
+        ];
+
+        for (s, right) in colors.iter() {
+            assert_eq!(s.parse::<Color>().unwrap().to_string(), *right);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_color() {
+        for s in [
+            "",
+            "#",
+            "ff",
+            "fffff",
+            "fffffff",
+            "#ggg",
+            "##fff",
+            "fffffffff",
+        ]
+        .iter()
+        {
+            assert!(matches!(s.parse::<Color>(), Err(Error::ParamError(_))));
+        }
+    }
+
+    #[test]
+    fn test_parse_named_color() {
+        let colors = [
+            ("red", "red"),
+            ("RebeccaPurple", "rebeccapurple"),
+            ("TRANSPARENT", "transparent"),
+            ("aliceblue", "aliceblue"),
+            ("yellowgreen", "yellowgreen"),
+        ];
+
+        for (s, right) in colors.iter() {
+            assert_eq!(s.parse::<Color>().unwrap().to_string(), *right);
+        }
+
+        for s in ["#red", "bleu", "light blue"].iter() {
+            assert!(matches!(s.parse::<Color>(), Err(Error::ParamError(_))));
+        }
+    }
+
+    #[test]
+    fn test_named_colors_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_color_components() {
+        assert_eq!(Color::rgb(0, 128, 255).to_string(), "0080ff");
+        assert_eq!(Color::argb(128, 0, 128, 255).to_string(), "800080ff");
+    }
+}
@@ -0,0 +1,228 @@
+//! Face detection parameters (i.e. `faceindex`, `facepad`, along with
+//! `fit=facearea` and `crop=faces`).
+use super::{value, Crop, Fit};
+use crate::{Error, Result, Url};
+
+impl Url {
+    /// Crop the image to the area around a detected face
+    /// (i.e. `fit=facearea&facepad=...`).
+    ///
+    /// `facepad` is the padding around the face, as a multiple of its
+    /// size (i.e. 1.0 is no padding, 2.0 doubles the area around the face).
+    ///
+    /// A `facepad` or `faceindex` is only valid along with `fit=facearea`
+    /// (or, for `faceindex`, `fit=crop&crop=faces`). These parameters can be
+    /// set in any order, so call `Url::validate()` to check the combination
+    /// before shipping the `Url`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("avatar.png")
+    ///     .w(64)
+    ///     .h(64)
+    ///     .facearea(1.5)
+    ///     .faceindex(2);
+    ///
+    /// let right = "https://example.domain.net/avatar.png?w=64&h=64&fit=facearea&facepad=1.5&faceindex=2";
+    /// assert_eq!(url.join(), right);
+    /// assert!(url.validate().is_ok());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `facepad` is not a positive number.
+    pub fn facearea(self, facepad: f32) -> Self {
+        match self.try_facearea(facepad) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Crop the image to the area around a detected face, see
+    /// `Url::facearea()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `facepad` is not a
+    /// positive number.
+    pub fn try_facearea(self, facepad: f32) -> Result<Self> {
+        self.fit(Fit::FaceArea).try_facepad(facepad)
+    }
+
+    /// Crop the image so that the detected faces are kept in frame
+    /// (i.e. `fit=crop&crop=faces`).
+    ///
+    /// Replacing `fit` or `crop` afterwards makes a `faceindex` invalid,
+    /// see `Url::validate()`.
+    pub fn face_crop(self) -> Self {
+        self.fit(Fit::Crop).crop(&[Crop::Faces])
+    }
+
+    /// Set the padding around the face used when `fit` is `Fit::FaceArea`
+    /// (i.e. `facepad`), see `Url::facearea()`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `facepad` is not a positive number.
+    pub fn facepad(self, facepad: f32) -> Self {
+        match self.try_facepad(facepad) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the padding around the face used when `fit` is `Fit::FaceArea`
+    /// (i.e. `facepad`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `facepad` is not a
+    /// positive number.
+    pub fn try_facepad(mut self, facepad: f32) -> Result<Self> {
+        // `NaN` is not finite either.
+        if !facepad.is_finite() || facepad <= 0.0 {
+            return Err(Error::RangeError(format!(
+                "`facepad` must be positive, found {}",
+                facepad
+            )));
+        }
+
+        self.set_param("facepad", facepad.to_string());
+        Ok(self)
+    }
+
+    /// Set which detected face, counting from 1, is cropped to
+    /// (i.e. `faceindex`).
+    ///
+    /// A `faceindex` is only valid along with `fit=facearea` or
+    /// `fit=crop&crop=faces`, in any order. Call `Url::validate()` to check
+    /// the combination before shipping the `Url`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `faceindex` is 0.
+    pub fn faceindex(self, faceindex: u32) -> Self {
+        match self.try_faceindex(faceindex) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set which detected face, counting from 1, is cropped to
+    /// (i.e. `faceindex`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `faceindex` is 0.
+    pub fn try_faceindex(mut self, faceindex: u32) -> Result<Self> {
+        if faceindex == 0 {
+            return Err(Error::RangeError(
+                "`faceindex` counts from 1, found 0".to_owned(),
+            ));
+        }
+
+        self.set_param("faceindex", faceindex.to_string());
+        Ok(self)
+    }
+}
+
+/// Check that `facepad` is only used along with `fit=facearea`, and that
+/// `faceindex` counts from 1 and is only used along with `fit=facearea`
+/// or `crop=faces`.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    let facearea = value(params, "fit") == Some("facearea");
+    let faces = value(params, "fit") == Some("crop")
+        && value(params, "crop").is_some_and(|crop| crop.split(',').any(|c| c == "faces"));
+
+    if value(params, "facepad").is_some() && !facearea {
+        return Err(Error::ParamError(
+            "cannot use a `facepad` without `fit=facearea`".to_owned(),
+        ));
+    }
+
+    if let Some(faceindex) = value(params, "faceindex") {
+        if !facearea && !faces {
+            return Err(Error::ParamError(
+                "cannot use a `faceindex` without `fit=facearea` or `fit=crop&crop=faces`"
+                    .to_owned(),
+            ));
+        }
+
+        if faceindex == "0" {
+            return Err(Error::ParamError(
+                "cannot use a `faceindex` of 0, it counts from 1".to_owned(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_face_crop() {
+        let url = Url::new(DOMAIN).path(PATH).face_crop().faceindex(1);
+        let right = "https://test.domain.com/image.png?fit=crop&crop=faces&faceindex=1";
+        assert_eq!(url.join(), right);
+
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .fit(Fit::Crop)
+            .crop(&[Crop::Faces, Crop::Entropy])
+            .faceindex(3);
+        assert!(url.validate().is_ok());
+    }
+
+    #[test]
+    fn test_facearea() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .face_crop()
+            .facearea(2.0)
+            .faceindex(4);
+        let right =
+            "https://test.domain.com/image.png?fit=facearea&crop=faces&facepad=2&faceindex=4";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_face_params_out_of_range() {
+        let results = [
+            Url::new(DOMAIN).try_facearea(0.0),
+            Url::new(DOMAIN).try_facepad(-1.0),
+            Url::new(DOMAIN).try_facepad(f32::NAN),
+            Url::new(DOMAIN).try_faceindex(0),
+        ];
+
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+
+    #[test]
+    fn test_face_params_combinations() {
+        let invalid = [
+            Url::new(DOMAIN).path(PATH).faceindex(1),
+            Url::new(DOMAIN).path(PATH).fit(Fit::Crop).faceindex(1),
+            Url::new(DOMAIN).path(PATH).face_crop().facepad(1.5),
+            Url::new(DOMAIN).path(PATH).params(&[
+                ("fit", "crop"),
+                ("crop", "faces"),
+                ("faceindex", "0"),
+            ]),
+        ];
+
+        for url in invalid.iter() {
+            assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+        }
+    }
+}
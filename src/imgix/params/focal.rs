@@ -0,0 +1,98 @@
+//! Focal point parameters (i.e. `fp-x`, `fp-y`, `fp-z`, `fp-debug`).
+use super::{Crop, Fit};
+use crate::{validate, Result, Url};
+
+impl Url {
+    /// Crop the image around a focal point (i.e. `fp-x`, `fp-y`, `fp-z`).
+    ///
+    /// `x` and `y` are fractions, from 0.0 to 1.0, of the width and height
+    /// of the image; `z` is the zoom, from 1.0 to 100.0. This also sets
+    /// `fit=crop&crop=focalpoint`, without which the focal point is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .w(320)
+    ///     .h(320)
+    ///     .focal_point(0.25, 0.75, 2.0);
+    ///
+    /// let right = "https://example.domain.net/image.png\
+    ///     ?w=320&h=320&fit=crop&crop=focalpoint&fp-x=0.25&fp-y=0.75&fp-z=2";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `x`, `y` or `z` is out of range.
+    pub fn focal_point(self, x: f32, y: f32, z: f32) -> Self {
+        match self.try_focal_point(x, y, z) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Crop the image around a focal point, see `Url::focal_point()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `x` or `y` is not
+    /// within 0.0 to 1.0, or if `z` is not within 1.0 to 100.0.
+    pub fn try_focal_point(self, x: f32, y: f32, z: f32) -> Result<Self> {
+        validate::in_range("fp-x", x, 0.0..=1.0)?;
+        validate::in_range("fp-y", y, 0.0..=1.0)?;
+        validate::in_range("fp-z", z, 1.0..=100.0)?;
+
+        let mut url = self.fit(Fit::Crop).crop(&[Crop::FocalPoint]);
+        url.set_param("fp-x", x.to_string());
+        url.set_param("fp-y", y.to_string());
+        url.set_param("fp-z", z.to_string());
+        Ok(url)
+    }
+
+    /// Set whether the focal point is drawn over the output image, to
+    /// check it during development (i.e. `fp-debug`).
+    pub fn fp_debug(mut self, state: bool) -> Self {
+        self.set_param("fp-debug", state.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_focal_point() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .crop(&[Crop::Faces])
+            .focal_point(0.0, 1.0, 1.0)
+            .focal_point(0.5, 0.125, 1.5)
+            .fp_debug(true);
+
+        let right = "https://test.domain.com/image.png?crop=focalpoint&fit=crop&fp-x=0.5&fp-y=0.125&fp-z=1.5&fp-debug=true";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_focal_point_out_of_range() {
+        let results = [
+            Url::new(DOMAIN).try_focal_point(-0.1, 0.5, 1.0),
+            Url::new(DOMAIN).try_focal_point(0.5, 1.1, 1.0),
+            Url::new(DOMAIN).try_focal_point(0.5, 0.5, 0.5),
+            Url::new(DOMAIN).try_focal_point(0.5, 0.5, 101.0),
+            Url::new(DOMAIN).try_focal_point(f32::NAN, 0.5, 1.0),
+        ];
+
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Format and compression parameters (i.e. `fm`, `auto`, `q`, `lossless`,
+//! `cs`, `chromasub`).
+use std::fmt::{self, Display};
+
+use crate::{validate, Error, Result, Url};
+
+/// The output format of an image (i.e. the `fm` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Avif,
+    Blurhash,
+    Gif,
+    Jp2,
+    Jpg,
+    Json,
+    Jxl,
+    Jxr,
+    Mp4,
+    Pjpg,
+    Png,
+    Png8,
+    Png32,
+    Webm,
+    Webp,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Format::Avif => "avif",
+            Format::Blurhash => "blurhash",
+            Format::Gif => "gif",
+            Format::Jp2 => "jp2",
+            Format::Jpg => "jpg",
+            Format::Json => "json",
+            Format::Jxl => "jxl",
+            Format::Jxr => "jxr",
+            Format::Mp4 => "mp4",
+            Format::Pjpg => "pjpg",
+            Format::Png => "png",
+            Format::Png8 => "png8",
+            Format::Png32 => "png32",
+            Format::Webm => "webm",
+            Format::Webp => "webp",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// An automatic optimization (i.e. a value of the `auto` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Auto {
+    Compress,
+    Enhance,
+    Format,
+    Redeye,
+}
+
+impl Display for Auto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Auto::Compress => "compress",
+            Auto::Enhance => "enhance",
+            Auto::Format => "format",
+            Auto::Redeye => "redeye",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// The color space of the output image (i.e. the `cs` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorSpace {
+    AdobeRgb1998,
+    Srgb,
+    Strip,
+    TinySrgb,
+}
+
+impl Display for ColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ColorSpace::AdobeRgb1998 => "adobergb1998",
+            ColorSpace::Srgb => "srgb",
+            ColorSpace::Strip => "strip",
+            ColorSpace::TinySrgb => "tinysrgb",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// The chroma subsampling of JPEG output (i.e. the `chromasub` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChromaSub {
+    Yuv420,
+    Yuv422,
+    Yuv444,
+}
+
+impl Display for ChromaSub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ChromaSub::Yuv420 => "420",
+            ChromaSub::Yuv422 => "422",
+            ChromaSub::Yuv444 => "444",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Url {
+    /// Set the output format (i.e. `fm`).
+    pub fn fm(mut self, fm: Format) -> Self {
+        self.set_param("fm", fm.to_string());
+        self
+    }
+
+    /// Request the metadata of the image, instead of the image itself, as
+    /// a JSON document (i.e. `fm=json`).
+    ///
+    /// See `imgix::metadata::ImageMetadata` for parsing the response.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net").path("image.jpg").metadata();
+    /// assert_eq!(url.join(), "https://example.domain.net/image.jpg?fm=json");
+    /// ```
+    pub fn metadata(self) -> Self {
+        self.fm(Format::Json)
+    }
+
+    /// Set the automatic optimizations to apply (i.e. `auto`).
+    ///
+    /// Repeated optimizations are only listed once.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Auto;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .auto(&[Auto::Format, Auto::Compress]);
+    ///
+    /// let right = "https://example.domain.net/image.png?auto=format%2Ccompress";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `auto` is empty.
+    pub fn auto(self, auto: &[Auto]) -> Self {
+        match self.try_auto(auto) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the automatic optimizations to apply (i.e. `auto`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `auto` is empty.
+    pub fn try_auto(mut self, auto: &[Auto]) -> Result<Self> {
+        if auto.is_empty() {
+            return Err(Error::ParamError("`auto` cannot be empty".to_owned()));
+        }
+
+        let mut values: Vec<String> = Vec::with_capacity(auto.len());
+        for a in auto.iter().map(Auto::to_string) {
+            if !values.contains(&a) {
+                values.push(a);
+            }
+        }
+        self.set_param("auto", values.join(","));
+        Ok(self)
+    }
+
+    /// Set the output quality, from 0 to 100 (i.e. `q`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `q` is greater than 100.
+    pub fn q(self, q: u8) -> Self {
+        match self.try_q(q) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the output quality, from 0 to 100 (i.e. `q`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `q` is greater than 100.
+    pub fn try_q(mut self, q: u8) -> Result<Self> {
+        validate::in_range("q", q, 0..=100)?;
+        self.set_param("q", q.to_string());
+        Ok(self)
+    }
+
+    /// Set whether lossless compression is used (i.e. `lossless`).
+    pub fn lossless(mut self, state: bool) -> Self {
+        self.set_param("lossless", if state { "1" } else { "0" }.to_owned());
+        self
+    }
+
+    /// Set the color space of the output image (i.e. `cs`).
+    pub fn cs(mut self, cs: ColorSpace) -> Self {
+        self.set_param("cs", cs.to_string());
+        self
+    }
+
+    /// Set the chroma subsampling of JPEG output (i.e. `chromasub`).
+    pub fn chromasub(mut self, chromasub: ChromaSub) -> Self {
+        self.set_param("chromasub", chromasub.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_fm() {
+        let formats = [
+            (Format::Avif, "avif"),
+            (Format::Blurhash, "blurhash"),
+            (Format::Gif, "gif"),
+            (Format::Jp2, "jp2"),
+            (Format::Jpg, "jpg"),
+            (Format::Json, "json"),
+            (Format::Jxl, "jxl"),
+            (Format::Jxr, "jxr"),
+            (Format::Mp4, "mp4"),
+            (Format::Pjpg, "pjpg"),
+            (Format::Png, "png"),
+            (Format::Png8, "png8"),
+            (Format::Png32, "png32"),
+            (Format::Webm, "webm"),
+            (Format::Webp, "webp"),
+        ];
+
+        for (fm, value) in formats.iter() {
+            let url = Url::new(DOMAIN).path(PATH).fm(*fm);
+            let right = Url::new(DOMAIN).path(PATH).param("fm", value);
+            assert_eq!(url.join(), right.join());
+        }
+    }
+
+    #[test]
+    fn test_metadata() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .fm(Format::Png)
+            .w(320)
+            .metadata();
+        let right = "https://test.domain.com/image.png?fm=json&w=320";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_auto() {
+        let url = Url::new(DOMAIN)
+            .auto(&[Auto::Format, Auto::Compress, Auto::Format])
+            .auto(&[Auto::Enhance, Auto::Redeye, Auto::Compress]);
+        assert_eq!(url.get_params()[0].1, "enhance,redeye,compress");
+
+        assert!(matches!(
+            Url::new(DOMAIN).try_auto(&[]),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_q() {
+        let url = Url::new(DOMAIN).q(0).q(100);
+        assert_eq!(url.get_params(), &[("q".to_owned(), "100".to_owned())]);
+        assert!(matches!(
+            Url::new(DOMAIN).try_q(101),
+            Err(Error::RangeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_compression_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .fm(Format::Webp)
+            .lossless(true)
+            .cs(ColorSpace::TinySrgb)
+            .chromasub(ChromaSub::Yuv444);
+
+        let right =
+            "https://test.domain.com/image.png?fm=webp&lossless=1&cs=tinysrgb&chromasub=444";
+        assert_eq!(url.join(), right);
+
+        let url = Url::new(DOMAIN)
+            .lossless(false)
+            .cs(ColorSpace::AdobeRgb1998);
+        assert_eq!(
+            url.get_params(),
+            &[
+                ("lossless".to_owned(), "0".to_owned()),
+                ("cs".to_owned(), "adobergb1998".to_owned()),
+            ]
+        );
+    }
+}
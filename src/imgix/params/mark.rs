@@ -0,0 +1,389 @@
+//! Watermark parameters (i.e. `mark`, `mark-align`, `mark-alpha`,
+//! `mark-pad`, `mark-scale`, `mark-w`, `mark-h`, `mark-fit`).
+use std::fmt::{self, Display};
+
+use super::{align, Align};
+use crate::{validate, Error, Result, Url};
+
+/// Controls how the watermark is resized to `mark-w` and `mark-h`
+/// (i.e. the `mark-fit` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkFit {
+    Clip,
+    Crop,
+    Fill,
+    Max,
+    Scale,
+}
+
+impl Display for MarkFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            MarkFit::Clip => "clip",
+            MarkFit::Crop => "crop",
+            MarkFit::Fill => "fill",
+            MarkFit::Max => "max",
+            MarkFit::Scale => "scale",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A watermark, comprised of the `mark` parameter and the `mark-*`
+/// parameters that position and style it.
+///
+/// The watermark image is either another imgix `Url`, which is joined
+/// (and signed with its own `token`) when the `Watermark` is constructed,
+/// or a raw URL string. Either way, the resulting URL is percent-encoded
+/// as a single parameter value of the outer `Url`, even if the outer `Url`
+/// has encoding turned off, so the outer signature covers the nested URL
+/// as-is.
+///
+/// A `Watermark` is attached to a `Url` with `Url::mark()` and is expanded
+/// into its parameters, in a fixed order, when the `Url` is joined.
+///
+/// # Examples
+/// ```
+/// use imgix::params::{Align, Watermark};
+/// use imgix::Url;
+///
+/// let logo = Url::new("assets.domain.net").path("logo.png").w(64);
+/// let mark = Watermark::new(&logo)
+///     .align(&[Align::Bottom, Align::Right])
+///     .alpha(80)
+///     .pad(10);
+///
+/// let url = Url::new("example.domain.net").path("image.png").mark(mark);
+///
+/// let right = "https://example.domain.net/image.png\
+///     ?mark=https%3A%2F%2Fassets.domain.net%2Flogo.png%3Fw%3D64\
+///     &mark-align=bottom%2Cright&mark-alpha=80&mark-pad=10";
+/// assert_eq!(url.join(), right);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Watermark {
+    mark: String,
+    align: Option<String>,
+    alpha: Option<u32>,
+    pad: Option<u32>,
+    scale: Option<u32>,
+    w: Option<u32>,
+    h: Option<u32>,
+    fit: Option<MarkFit>,
+}
+
+impl Watermark {
+    /// Construct a new `Watermark` of the image at the imgix `Url` `mark`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `mark` cannot be joined, see `Url::join()`.
+    pub fn new(mark: &Url) -> Self {
+        match Watermark::try_new(mark) {
+            Ok(watermark) => watermark,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Construct a new `Watermark` of the image at the imgix `Url` `mark`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::JoinError` if `mark` cannot be
+    /// joined, see `Url::try_join()`.
+    pub fn try_new(mark: &Url) -> Result<Self> {
+        Ok(Watermark::with_mark(mark.try_join()?))
+    }
+
+    /// Construct a new `Watermark` of the image at the raw URL `mark`
+    /// (i.e. "https://assets.domain.net/logo.png").
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `mark` is an empty string.
+    pub fn raw(mark: &str) -> Self {
+        match Watermark::try_raw(mark) {
+            Ok(watermark) => watermark,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Construct a new `Watermark` of the image at the raw URL `mark`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParamError` if `mark` is an empty
+    /// string.
+    pub fn try_raw(mark: &str) -> Result<Self> {
+        if mark.is_empty() {
+            return Err(Error::ParamError("`mark` cannot be empty".to_owned()));
+        }
+        Ok(Watermark::with_mark(String::from(mark)))
+    }
+
+    fn with_mark(mark: String) -> Self {
+        Watermark {
+            mark,
+            align: None,
+            alpha: None,
+            pad: None,
+            scale: None,
+            w: None,
+            h: None,
+            fit: None,
+        }
+    }
+
+    /// Set the alignment of the watermark (i.e. `mark-align`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `align` is empty or if it contains more than
+    /// one vertical or more than one horizontal alignment.
+    pub fn align(self, align: &[Align]) -> Self {
+        match self.try_align(align) {
+            Ok(watermark) => watermark,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the alignment of the watermark (i.e. `mark-align`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `align` is empty or if
+    /// it contains more than one vertical or more than one horizontal
+    /// alignment.
+    pub fn try_align(mut self, align: &[Align]) -> Result<Self> {
+        self.align = Some(align::join("mark-align", align)?);
+        Ok(self)
+    }
+
+    /// Set the opacity of the watermark, from 0 to 100 (i.e. `mark-alpha`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `alpha` is greater than 100.
+    pub fn alpha(self, alpha: u32) -> Self {
+        match self.try_alpha(alpha) {
+            Ok(watermark) => watermark,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the opacity of the watermark, from 0 to 100 (i.e. `mark-alpha`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `alpha` is greater
+    /// than 100.
+    pub fn try_alpha(mut self, alpha: u32) -> Result<Self> {
+        validate::in_range("mark-alpha", alpha, 0..=100)?;
+        self.alpha = Some(alpha);
+        Ok(self)
+    }
+
+    /// Set the padding, in pixels, between the watermark and the edges of
+    /// the image (i.e. `mark-pad`).
+    pub fn pad(mut self, pad: u32) -> Self {
+        self.pad = Some(pad);
+        self
+    }
+
+    /// Set the width of the watermark as a percentage of the width of the
+    /// image, from 0 to 100 (i.e. `mark-scale`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `scale` is greater than 100.
+    pub fn scale(self, scale: u32) -> Self {
+        match self.try_scale(scale) {
+            Ok(watermark) => watermark,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the width of the watermark as a percentage of the width of the
+    /// image, from 0 to 100 (i.e. `mark-scale`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `scale` is greater
+    /// than 100.
+    pub fn try_scale(mut self, scale: u32) -> Result<Self> {
+        validate::in_range("mark-scale", scale, 0..=100)?;
+        self.scale = Some(scale);
+        Ok(self)
+    }
+
+    /// Set the width, in pixels, of the watermark (i.e. `mark-w`).
+    pub fn w(mut self, w: u32) -> Self {
+        self.w = Some(w);
+        self
+    }
+
+    /// Set the height, in pixels, of the watermark (i.e. `mark-h`).
+    pub fn h(mut self, h: u32) -> Self {
+        self.h = Some(h);
+        self
+    }
+
+    /// Set how the watermark is resized to its width and height
+    /// (i.e. `mark-fit`).
+    pub fn fit(mut self, fit: MarkFit) -> Self {
+        self.fit = Some(fit);
+        self
+    }
+
+    /// Expand this watermark into its key-value parameters.
+    pub(crate) fn to_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![("mark".to_owned(), self.mark.clone())];
+        let mut push = |k: &str, v: Option<String>| {
+            if let Some(v) = v {
+                params.push((k.to_owned(), v));
+            }
+        };
+
+        push("mark-align", self.align.clone());
+        push("mark-alpha", self.alpha.map(|v| v.to_string()));
+        push("mark-pad", self.pad.map(|v| v.to_string()));
+        push("mark-scale", self.scale.map(|v| v.to_string()));
+        push("mark-w", self.w.map(|v| v.to_string()));
+        push("mark-h", self.h.map(|v| v.to_string()));
+        push("mark-fit", self.fit.map(|v| v.to_string()));
+        params
+    }
+}
+
+impl Url {
+    /// Attach a watermark, replacing any watermark attached previously.
+    ///
+    /// The watermark's parameters follow this `Url`'s own parameters, and
+    /// any text overlay, in the query string, see `Watermark`. Any `mark*`
+    /// parameters set before are removed, and none can be set with
+    /// `Url::param()` after.
+    pub fn mark(mut self, watermark: Watermark) -> Self {
+        self.set_mark(watermark);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_mark_params_order() {
+        let mark = Watermark::raw("https://assets.domain.com/logo.png")
+            .fit(MarkFit::Clip)
+            .h(40)
+            .w(120)
+            .scale(25)
+            .pad(5)
+            .alpha(0)
+            .align(&[Align::Middle]);
+
+        let right: Vec<(String, String)> = [
+            ("mark", "https://assets.domain.com/logo.png"),
+            ("mark-align", "middle"),
+            ("mark-alpha", "0"),
+            ("mark-pad", "5"),
+            ("mark-scale", "25"),
+            ("mark-w", "120"),
+            ("mark-h", "40"),
+            ("mark-fit", "clip"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(mark.to_params(), right);
+    }
+
+    #[test]
+    fn test_mark_signs_both_levels() {
+        let logo = Url::new("assets.domain.com")
+            .path("logo.png")
+            .w(64)
+            .token("inner");
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .w(640)
+            .mark(Watermark::new(&logo).alpha(50))
+            .token("outer");
+
+        let right = "https://test.domain.com/image.png?w=640\
+            &mark=https%3A%2F%2Fassets.domain.com%2Flogo.png%3Fw%3D64%26s%3D85da83d946375f9d1951f7d3ad7dbae5\
+            &mark-alpha=50&s=d0c01b8ff7520d38f8e2d2971eb831bc";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_mark_replaces_previous() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .mark(Watermark::raw("/first.png").pad(5))
+            .mark(Watermark::raw("/second.png"));
+
+        let right = "https://test.domain.com/image.png?mark=%2Fsecond.png";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_mark_encode_disabled() {
+        let logo = Url::new("a.net").path("logo.png").w(64).h(10);
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .param("ar", "16%3A9")
+            .mark(Watermark::new(&logo).align(&[Align::Top, Align::Left]))
+            .encode(false);
+
+        let right = "https://test.domain.com/image.png?ar=16%3A9\
+            &mark=https%3A%2F%2Fa.net%2Flogo.png%3Fw%3D64%26h%3D10&mark-align=top%2Cleft";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_mark_replaces_params() {
+        let url: Url = "https://test.domain.com/image.png?mark=%2Fold.png&w=10&mark-pad=5"
+            .parse()
+            .unwrap();
+        let url = url.mark(Watermark::raw("/new.png"));
+
+        let right = "https://test.domain.com/image.png?w=10&mark=%2Fnew.png";
+        assert_eq!(url.join(), right);
+
+        // Nor can they be set again once a watermark is attached.
+        for k in ["mark", "mark64", "mark-pad"].iter() {
+            assert!(matches!(
+                Url::new(DOMAIN)
+                    .mark(Watermark::raw("/logo.png"))
+                    .try_param(k, "b"),
+                Err(Error::ParamError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_mark_invalid() {
+        assert!(matches!(Watermark::try_raw(""), Err(Error::ParamError(_))));
+        assert!(matches!(
+            Watermark::try_new(&Url::new(DOMAIN)),
+            Err(Error::JoinError(_))
+        ));
+        assert!(matches!(
+            Watermark::raw("/logo.png").try_alpha(101),
+            Err(Error::RangeError(_))
+        ));
+        assert!(matches!(
+            Watermark::raw("/logo.png").try_scale(101),
+            Err(Error::RangeError(_))
+        ));
+        assert!(matches!(
+            Watermark::raw("/logo.png").try_align(&[]),
+            Err(Error::ParamError(_))
+        ));
+    }
+}
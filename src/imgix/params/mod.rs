@@ -0,0 +1,96 @@
+//! Typed rendering parameters.
+//!
+//! Each sub-module extends `Url` with building functions for one family
+//! of imgix rendering parameters (i.e. size, format). These functions
+//! serialize to the same key-value pairs that could be passed to
+//! `Url::param()`, but the compiler checks their keys and values.
+//!
+//! Setting a typed parameter that has already been set replaces its
+//! value in-place, so parameters keep the order in which they were
+//! first defined. Combinations of parameters that imgix does not support
+//! (i.e. `corner-radius` without `mask=corners`, `fill-color` without
+//! `fill=solid`) are reported by `Url::validate()`.
+
+/// Define a panicking and a fallible building function for a parameter
+/// `$key` whose values, of type `$ty`, are within `$range`.
+macro_rules! ranged_param {
+    ($(#[$doc:meta])* $name:ident, $try_name:ident, $key:expr, $range:expr, $ty:ty) => {
+        $(#[$doc])*
+        ///
+        /// # Panics
+        ///
+        /// This method panics if the value is out of range.
+        pub fn $name(self, v: $ty) -> Self {
+            match self.$try_name(v) {
+                Ok(url) => url,
+                Err(e) => panic!("{}", e),
+            }
+        }
+
+        $(#[$doc])*
+        ///
+        /// # Errors
+        ///
+        /// This method returns an `Error::RangeError` if the value is out
+        /// of range.
+        pub fn $try_name(mut self, v: $ty) -> Result<Self> {
+            validate::in_range($key, v, $range)?;
+            self.set_param($key, v.to_string());
+            Ok(self)
+        }
+    };
+}
+
+mod adjust;
+mod align;
+mod animate;
+mod blend;
+mod border;
+mod color;
+mod face;
+mod focal;
+mod format;
+mod mark;
+mod palette;
+mod rotate;
+mod size;
+mod stylize;
+mod text;
+mod trim;
+
+pub use self::align::Align;
+pub use self::blend::{BlendMode, BlendSize, Mask};
+pub use self::color::Color;
+pub use self::format::{Auto, ChromaSub, ColorSpace, Format};
+pub use self::mark::{MarkFit, Watermark};
+pub use self::palette::PaletteFormat;
+pub use self::rotate::{Flip, Orient};
+pub use self::size::{Crop, Fit, Rect};
+pub use self::text::{TextFit, TextOverlay};
+pub use self::trim::{Fill, Trim};
+
+use crate::Result;
+
+/// Check that the typed parameters in `params` are combined in a way
+/// imgix supports, see `Url::validate()`.
+///
+/// # Errors
+///
+/// This function returns an `Error::ParamError` describing the first
+/// unsupported combination found.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    animate::check(params)?;
+    blend::check(params)?;
+    face::check(params)?;
+    palette::check(params)?;
+    trim::check(params)?;
+    Ok(())
+}
+
+/// Get the value of the parameter `k`, if it has been set.
+fn value<'a>(params: &'a [(String, String)], k: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == k)
+        .map(|(_, v)| v.as_str())
+}
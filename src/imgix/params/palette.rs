@@ -0,0 +1,149 @@
+//! Palette extraction parameters (i.e. `palette`, `colors`, `prefix`).
+//!
+//! See `imgix::palette` for parsing the palettes these requests return.
+use std::fmt::{self, Display};
+
+use super::value;
+use crate::{validate, Error, Result, Url};
+
+/// The format a palette is returned in (i.e. the `palette` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaletteFormat {
+    /// A CSS stylesheet of color classes, see `Url::prefix()`.
+    Css,
+    /// A JSON document, see `imgix::palette::Palette`.
+    Json,
+}
+
+impl Display for PaletteFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            PaletteFormat::Css => "css",
+            PaletteFormat::Json => "json",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Url {
+    /// Request the color palette of the image, instead of the image
+    /// itself, in the given `format` (i.e. `palette`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::PaletteFormat;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .palette(PaletteFormat::Css)
+    ///     .colors(4)
+    ///     .prefix("hero");
+    ///
+    /// let right = "https://example.domain.net/image.png?palette=css&colors=4&prefix=hero";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    pub fn palette(mut self, format: PaletteFormat) -> Self {
+        self.set_param("palette", format.to_string());
+        self
+    }
+
+    ranged_param!(
+        /// Set the number of colors in the palette, from 0 to 16
+        /// (i.e. `colors`).
+        colors,
+        try_colors,
+        "colors",
+        0..=16,
+        u32
+    );
+
+    /// Set the prefix of the class names of a CSS palette (i.e. `prefix`).
+    ///
+    /// A `prefix` is only valid if the `palette` is `PaletteFormat::Css`,
+    /// see `Url::validate()`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `prefix` is not a valid CSS class name.
+    pub fn prefix(self, prefix: &str) -> Self {
+        match self.try_prefix(prefix) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the prefix of the class names of a CSS palette (i.e. `prefix`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `prefix` is empty,
+    /// starts with a digit, or contains characters other than ASCII
+    /// letters, digits, `-` and `_`.
+    pub fn try_prefix(mut self, prefix: &str) -> Result<Self> {
+        let valid = prefix
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !valid || prefix.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(Error::ParamError(format!(
+                "`prefix` must be a CSS class name, found `{}`",
+                prefix
+            )));
+        }
+
+        validate::param_pair("prefix", prefix)?;
+        self.set_param("prefix", String::from(prefix));
+        Ok(self)
+    }
+}
+
+/// Check that `prefix` is only used along with `palette=css`.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    if value(params, "prefix").is_some() && value(params, "palette") != Some("css") {
+        return Err(Error::ParamError(
+            "cannot use a `prefix` without `palette=css`".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_palette_json() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .palette(PaletteFormat::Json)
+            .colors(16);
+        let right = "https://test.domain.com/image.png?palette=json&colors=16";
+        assert_eq!(url.join(), right);
+
+        assert!(matches!(
+            Url::new(DOMAIN).try_colors(17),
+            Err(Error::RangeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_prefix() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .palette(PaletteFormat::Json)
+            .prefix("brand_");
+        assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+
+        for prefix in ["", "1st", "two words", "dot.ted"].iter() {
+            assert!(matches!(
+                Url::new(DOMAIN).try_prefix(prefix),
+                Err(Error::ParamError(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Rotation parameters (i.e. `rot`, `flip`, `orient`).
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+
+use crate::{validate, Error, Result, Url};
+
+/// The axes an image is flipped along (i.e. the `flip` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flip {
+    /// Flip horizontally.
+    H,
+    /// Flip vertically.
+    V,
+    /// Flip both horizontally and vertically.
+    Hv,
+}
+
+impl Display for Flip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Flip::H => "h",
+            Flip::V => "v",
+            Flip::Hv => "hv",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// The orientation of an image (i.e. the `orient` parameter).
+///
+/// The first eight variants are the EXIF orientations 1 through 8, named
+/// after the position of the image's 0th row and 0th column. The last
+/// three rotate the image counter-clockwise by 90, 180 or 270 degrees.
+///
+/// # Examples
+/// ```
+/// use std::convert::TryFrom;
+/// use imgix::params::Orient;
+///
+/// assert_eq!(Orient::try_from(6).unwrap(), Orient::RightTop);
+/// assert_eq!(Orient::RightTop.to_string(), "6");
+/// assert!(Orient::try_from(9).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orient {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+    LeftTop,
+    RightTop,
+    RightBottom,
+    LeftBottom,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl TryFrom<u16> for Orient {
+    type Error = Error;
+
+    /// Convert an EXIF orientation, from 1 to 8, or a rotation of 90, 180
+    /// or 270 degrees into an `Orient`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParamError` if `value` is not one
+    /// of the values listed above.
+    fn try_from(value: u16) -> Result<Self> {
+        let orient = match value {
+            1 => Orient::TopLeft,
+            2 => Orient::TopRight,
+            3 => Orient::BottomRight,
+            4 => Orient::BottomLeft,
+            5 => Orient::LeftTop,
+            6 => Orient::RightTop,
+            7 => Orient::RightBottom,
+            8 => Orient::LeftBottom,
+            90 => Orient::Rotate90,
+            180 => Orient::Rotate180,
+            270 => Orient::Rotate270,
+            _ => {
+                return Err(Error::ParamError(format!(
+                    "`orient` must be 1 to 8, 90, 180 or 270, found {}",
+                    value
+                )))
+            }
+        };
+        Ok(orient)
+    }
+}
+
+impl Display for Orient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Orient::TopLeft => "1",
+            Orient::TopRight => "2",
+            Orient::BottomRight => "3",
+            Orient::BottomLeft => "4",
+            Orient::LeftTop => "5",
+            Orient::RightTop => "6",
+            Orient::RightBottom => "7",
+            Orient::LeftBottom => "8",
+            Orient::Rotate90 => "90",
+            Orient::Rotate180 => "180",
+            Orient::Rotate270 => "270",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Url {
+    /// Set the rotation of the image, in degrees from 0 to 359 (i.e. `rot`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rot` is greater than 359.
+    pub fn rot(self, rot: u32) -> Self {
+        match self.try_rot(rot) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the rotation of the image, in degrees from 0 to 359 (i.e. `rot`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `rot` is greater
+    /// than 359.
+    pub fn try_rot(mut self, rot: u32) -> Result<Self> {
+        validate::in_range("rot", rot, 0..=359)?;
+        self.set_param("rot", rot.to_string());
+        Ok(self)
+    }
+
+    /// Set the axes the image is flipped along (i.e. `flip`).
+    pub fn flip(mut self, flip: Flip) -> Self {
+        self.set_param("flip", flip.to_string());
+        self
+    }
+
+    /// Set the orientation of the image (i.e. `orient`).
+    pub fn orient(mut self, orient: Orient) -> Self {
+        self.set_param("orient", orient.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_rotation_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .rot(0)
+            .flip(Flip::Hv)
+            .orient(Orient::Rotate270)
+            .rot(359);
+
+        let right = "https://test.domain.com/image.png?rot=359&flip=hv&orient=270";
+        assert_eq!(url.join(), right);
+
+        assert!(matches!(
+            Url::new(DOMAIN).try_rot(360),
+            Err(Error::RangeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_orient_try_from() {
+        let values = [1, 2, 3, 4, 5, 6, 7, 8, 90, 180, 270];
+        for value in values.iter() {
+            let orient = Orient::try_from(*value).unwrap();
+            assert_eq!(orient.to_string(), value.to_string());
+        }
+
+        for value in [0, 9, 45, 360].iter() {
+            assert!(matches!(
+                Orient::try_from(*value),
+                Err(Error::ParamError(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,515 @@
+//! Size parameters (i.e. `w`, `h`, `fit`, `crop`, `ar`, `rect`).
+use std::fmt::{self, Display};
+
+use crate::{validate, Error, Result, Url};
+
+/// Controls how the output image is fit to its target dimensions
+/// (i.e. the `fit` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fit {
+    Clamp,
+    Clip,
+    Crop,
+    FaceArea,
+    Fill,
+    FillMax,
+    Max,
+    Min,
+    Scale,
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Fit::Clamp => "clamp",
+            Fit::Clip => "clip",
+            Fit::Crop => "crop",
+            Fit::FaceArea => "facearea",
+            Fit::Fill => "fill",
+            Fit::FillMax => "fillmax",
+            Fit::Max => "max",
+            Fit::Min => "min",
+            Fit::Scale => "scale",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// Controls how the input image is aligned when `fit` is `Fit::Crop`
+/// (i.e. the `crop` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crop {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Faces,
+    FocalPoint,
+    Edges,
+    Entropy,
+}
+
+impl Display for Crop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Crop::Top => "top",
+            Crop::Bottom => "bottom",
+            Crop::Left => "left",
+            Crop::Right => "right",
+            Crop::Faces => "faces",
+            Crop::FocalPoint => "focalpoint",
+            Crop::Edges => "edges",
+            Crop::Entropy => "entropy",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A rectangle of the source image to crop to before any other
+/// transformation is applied (i.e. the `rect` parameter).
+///
+/// Rectangles are given by their top-left corner, `x` and `y`, and their
+/// width and height, `w` and `h`, either in pixels or as fractions of the
+/// source image's dimensions. Fractions are always written with a decimal
+/// point (i.e. `1.0` rather than `1`) so imgix does not read them as pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rect {
+    Pixels { x: u32, y: u32, w: u32, h: u32 },
+    Fraction { x: f32, y: f32, w: f32, h: f32 },
+}
+
+impl Rect {
+    /// Validate this rectangle.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::RangeError` if a fraction is not
+    /// within 0.0 to 1.0, or if the rectangle extends past the right or
+    /// bottom edge of the image, and an `Error::ParamError` if the rectangle
+    /// is empty.
+    fn validate(&self) -> Result<()> {
+        let empty = match *self {
+            Rect::Pixels { w, h, .. } => w == 0 || h == 0,
+            Rect::Fraction { x, y, w, h } => {
+                validate::in_range("rect", x, 0.0..=1.0)?;
+                validate::in_range("rect", y, 0.0..=1.0)?;
+                validate::in_range("rect", w, 0.0..=1.0)?;
+                validate::in_range("rect", h, 0.0..=1.0)?;
+
+                // Allow for rounding, i.e. 0.3 + 0.7 may exceed 1.0.
+                if x + w > 1.0 + f32::EPSILON || y + h > 1.0 + f32::EPSILON {
+                    return Err(Error::RangeError(format!(
+                        "`rect` must be within the image, found {}",
+                        self
+                    )));
+                }
+                w == 0.0 || h == 0.0
+            }
+        };
+
+        if empty {
+            return Err(Error::ParamError(format!(
+                "`rect` cannot be empty, found {}",
+                self
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rect::Pixels { x, y, w, h } => write!(f, "{},{},{},{}", x, y, w, h),
+            Rect::Fraction { x, y, w, h } => write!(
+                f,
+                "{},{},{},{}",
+                fraction(*x),
+                fraction(*y),
+                fraction(*w),
+                fraction(*h)
+            ),
+        }
+    }
+}
+
+/// Format the fraction `v` with a decimal point, even if it is a whole
+/// number (i.e. `1.0` rather than `1`).
+fn fraction(v: f32) -> String {
+    let s = v.to_string();
+    if s.contains('.') {
+        s
+    } else {
+        format!("{}.0", s)
+    }
+}
+
+impl Url {
+    /// Set the width of the output image (i.e. `w`).
+    pub fn w(mut self, w: u32) -> Self {
+        self.set_param("w", w.to_string());
+        self
+    }
+
+    /// Set the height of the output image (i.e. `h`).
+    pub fn h(mut self, h: u32) -> Self {
+        self.set_param("h", h.to_string());
+        self
+    }
+
+    /// Set the minimum width of the output image (i.e. `min-w`).
+    pub fn min_w(mut self, w: u32) -> Self {
+        self.set_param("min-w", w.to_string());
+        self
+    }
+
+    /// Set the minimum height of the output image (i.e. `min-h`).
+    pub fn min_h(mut self, h: u32) -> Self {
+        self.set_param("min-h", h.to_string());
+        self
+    }
+
+    /// Set the maximum width of the output image (i.e. `max-w`).
+    pub fn max_w(mut self, w: u32) -> Self {
+        self.set_param("max-w", w.to_string());
+        self
+    }
+
+    /// Set the maximum height of the output image (i.e. `max-h`).
+    pub fn max_h(mut self, h: u32) -> Self {
+        self.set_param("max-h", h.to_string());
+        self
+    }
+
+    /// Set how the output image is fit to its dimensions (i.e. `fit`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::{Crop, Fit};
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .w(320)
+    ///     .h(640)
+    ///     .fit(Fit::Crop)
+    ///     .crop(&[Crop::Top, Crop::Left]);
+    ///
+    /// let right = "https://example.domain.net/image.png?w=320&h=640&fit=crop&crop=top%2Cleft";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.set_param("fit", fit.to_string());
+        self
+    }
+
+    /// Set the crop mode(s) used when `fit` is `Fit::Crop` (i.e. `crop`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `crop` is empty.
+    pub fn crop(self, crop: &[Crop]) -> Self {
+        match self.try_crop(crop) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the crop mode(s) used when `fit` is `Fit::Crop` (i.e. `crop`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `crop` is empty.
+    pub fn try_crop(mut self, crop: &[Crop]) -> Result<Self> {
+        if crop.is_empty() {
+            return Err(Error::ParamError("`crop` cannot be empty".to_owned()));
+        }
+
+        let value = crop
+            .iter()
+            .map(Crop::to_string)
+            .collect::<Vec<String>>()
+            .join(",");
+        self.set_param("crop", value);
+        Ok(self)
+    }
+
+    /// Set the aspect ratio, `w:h`, of the output image (i.e. `ar`).
+    ///
+    /// The aspect ratio is only applied when `fit` is `Fit::Crop`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if either `w` or `h` is not a positive number.
+    pub fn ar(self, w: f32, h: f32) -> Self {
+        match self.try_ar(w, h) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the aspect ratio, `w:h`, of the output image (i.e. `ar`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if either `w` or `h` is
+    /// not a positive number.
+    pub fn try_ar(mut self, w: f32, h: f32) -> Result<Self> {
+        // This comparison is also `false` for `NaN`.
+        if !(w > 0.0 && h > 0.0) || w.is_infinite() || h.is_infinite() {
+            return Err(Error::ParamError(format!(
+                "`ar` must be positive, found {}:{}",
+                w, h
+            )));
+        }
+
+        self.set_param("ar", format!("{}:{}", w, h));
+        Ok(self)
+    }
+
+    /// Crop the source image to `rect` before any other transformation is
+    /// applied (i.e. `rect`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Rect;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .rect(Rect::Pixels { x: 10, y: 20, w: 300, h: 200 });
+    /// assert_eq!(url.join(), "https://example.domain.net/image.png?rect=10%2C20%2C300%2C200");
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .rect(Rect::Fraction { x: 0.25, y: 0.0, w: 0.5, h: 1.0 });
+    /// assert_eq!(url.join(), "https://example.domain.net/image.png?rect=0.25%2C0.0%2C0.5%2C1.0");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rect` is empty, if a fraction is not within
+    /// 0.0 to 1.0, or if `rect` extends past the edges of the image.
+    pub fn rect(self, rect: Rect) -> Self {
+        match self.try_rect(rect) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Crop the source image to `rect`, see `Url::rect()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `rect` is empty and
+    /// an `Error::RangeError` if a fraction is not within 0.0 to 1.0 or if
+    /// `rect` extends past the edges of the image.
+    pub fn try_rect(mut self, rect: Rect) -> Result<Self> {
+        rect.validate()?;
+        self.set_param("rect", rect.to_string());
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_size_params() {
+        let url = Url::new(DOMAIN)
+            .w(320)
+            .h(640)
+            .min_w(100)
+            .min_h(200)
+            .max_w(1000)
+            .max_h(2000);
+
+        let right = Url::new(DOMAIN).params(&[
+            ("w", "320"),
+            ("h", "640"),
+            ("min-w", "100"),
+            ("min-h", "200"),
+            ("max-w", "1000"),
+            ("max-h", "2000"),
+        ]);
+        assert_eq!(url.get_params(), right.get_params());
+    }
+
+    #[test]
+    fn test_fit() {
+        let fits = [
+            (Fit::Clamp, "clamp"),
+            (Fit::Clip, "clip"),
+            (Fit::Crop, "crop"),
+            (Fit::FaceArea, "facearea"),
+            (Fit::Fill, "fill"),
+            (Fit::FillMax, "fillmax"),
+            (Fit::Max, "max"),
+            (Fit::Min, "min"),
+            (Fit::Scale, "scale"),
+        ];
+
+        for (fit, value) in fits.iter() {
+            let url = Url::new(DOMAIN).path(PATH).fit(*fit);
+            let right = Url::new(DOMAIN).path(PATH).param("fit", value);
+            assert_eq!(url.join(), right.join());
+        }
+    }
+
+    #[test]
+    fn test_crop() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .fit(Fit::Crop)
+            .crop(&[Crop::Faces, Crop::Entropy]);
+        let right = "https://test.domain.com/image.png?fit=crop&crop=faces%2Centropy";
+        assert_eq!(url.join(), right);
+
+        let url = Url::new(DOMAIN).path(PATH).crop(&[Crop::FocalPoint]);
+        assert_eq!(url.get_params()[0].1, "focalpoint");
+
+        assert!(matches!(
+            Url::new(DOMAIN).try_crop(&[]),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_ar() {
+        let url = Url::new(DOMAIN).path(PATH).ar(16.0, 9.0).fit(Fit::Crop);
+        let right = Url::new(DOMAIN)
+            .path(PATH)
+            .params(&[("ar", "16:9"), ("fit", "crop")]);
+        assert_eq!(url.join(), right.join());
+
+        let url = Url::new(DOMAIN).ar(2.35, 1.0);
+        assert_eq!(url.get_params()[0].1, "2.35:1");
+
+        assert!(Url::new(DOMAIN).try_ar(0.0, 9.0).is_err());
+        assert!(Url::new(DOMAIN).try_ar(16.0, -9.0).is_err());
+        assert!(Url::new(DOMAIN).try_ar(f32::NAN, 9.0).is_err());
+        assert!(Url::new(DOMAIN).try_ar(f32::INFINITY, 9.0).is_err());
+    }
+
+    #[test]
+    fn test_rect() {
+        let url = Url::new(DOMAIN).rect(Rect::Pixels {
+            x: 0,
+            y: 0,
+            w: 1,
+            h: 1,
+        });
+        assert_eq!(url.get_params()[0].1, "0,0,1,1");
+
+        let url = Url::new(DOMAIN).rect(Rect::Fraction {
+            x: 0.1,
+            y: 0.2,
+            w: 0.75,
+            h: 0.5,
+        });
+        assert_eq!(url.get_params()[0].1, "0.1,0.2,0.75,0.5");
+
+        // Whole fractions are not mistaken for pixels.
+        let url = Url::new(DOMAIN).rect(Rect::Fraction {
+            x: 0.0,
+            y: 0.0,
+            w: 1.0,
+            h: 1.0,
+        });
+        assert_eq!(url.get_params()[0].1, "0.0,0.0,1.0,1.0");
+
+        let url = Url::new(DOMAIN).rect(Rect::Fraction {
+            x: 0.3,
+            y: 0.5,
+            w: 0.7,
+            h: 0.5,
+        });
+        assert_eq!(url.get_params()[0].1, "0.3,0.5,0.7,0.5");
+    }
+
+    #[test]
+    fn test_rect_invalid() {
+        let empty = [
+            Rect::Pixels {
+                x: 10,
+                y: 10,
+                w: 0,
+                h: 100,
+            },
+            Rect::Fraction {
+                x: 0.0,
+                y: 0.0,
+                w: 0.5,
+                h: 0.0,
+            },
+        ];
+        for rect in empty.iter() {
+            assert!(matches!(
+                Url::new(DOMAIN).try_rect(*rect),
+                Err(Error::ParamError(_))
+            ));
+        }
+
+        let out_of_range = [
+            Rect::Fraction {
+                x: -0.1,
+                y: 0.0,
+                w: 0.5,
+                h: 0.5,
+            },
+            Rect::Fraction {
+                x: 0.0,
+                y: 0.0,
+                w: 1.5,
+                h: 0.5,
+            },
+            Rect::Fraction {
+                x: 0.0,
+                y: f32::NAN,
+                w: 0.5,
+                h: 0.5,
+            },
+            Rect::Fraction {
+                x: 0.5,
+                y: 0.0,
+                w: 0.75,
+                h: 1.0,
+            },
+            Rect::Fraction {
+                x: 0.0,
+                y: 0.25,
+                w: 1.0,
+                h: 1.0,
+            },
+        ];
+        for rect in out_of_range.iter() {
+            assert!(matches!(
+                Url::new(DOMAIN).try_rect(*rect),
+                Err(Error::RangeError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_typed_params_replace() {
+        let url = Url::new(DOMAIN).w(320).h(640).w(480);
+        let right = Url::new(DOMAIN).params(&[("w", "480"), ("h", "640")]);
+        assert_eq!(url.get_params(), right.get_params());
+    }
+
+    #[test]
+    fn test_typed_params_replace_duplicates() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .param("w", "1")
+            .param("h", "2")
+            .param("w", "2")
+            .w(3);
+        assert_eq!(url.join(), "https://test.domain.com/image.png?w=3&h=2");
+    }
+}
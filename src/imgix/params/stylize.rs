@@ -0,0 +1,126 @@
+//! Stylize parameters (i.e. `blur`, `px`, `sepia`, `monochrome`,
+//! `duotone`, `duotone-alpha`, `htn`, `invert`).
+use super::Color;
+use crate::{validate, Result, Url};
+
+impl Url {
+    ranged_param!(
+        /// Set the radius of the Gaussian blur, from 0 to 2000 (i.e. `blur`).
+        blur,
+        try_blur,
+        "blur",
+        0..=2000,
+        u32
+    );
+
+    ranged_param!(
+        /// Set the size, in pixels, of the pixelation, from 0 to 100
+        /// (i.e. `px`).
+        px,
+        try_px,
+        "px",
+        0..=100,
+        u32
+    );
+
+    ranged_param!(
+        /// Set the strength of the sepia toning, from 0 to 100 (i.e. `sepia`).
+        sepia,
+        try_sepia,
+        "sepia",
+        0..=100,
+        u32
+    );
+
+    ranged_param!(
+        /// Set the width, in pixels, of the halftone dots, from 0 to 100
+        /// (i.e. `htn`).
+        htn,
+        try_htn,
+        "htn",
+        0..=100,
+        u32
+    );
+
+    ranged_param!(
+        /// Set the opacity of the duotone, from 0 to 100
+        /// (i.e. `duotone-alpha`).
+        duotone_alpha,
+        try_duotone_alpha,
+        "duotone-alpha",
+        0..=100,
+        u32
+    );
+
+    /// Tint the image with a single `color` (i.e. `monochrome`).
+    pub fn monochrome(mut self, color: Color) -> Self {
+        self.set_param("monochrome", color.to_string());
+        self
+    }
+
+    /// Map the shadows of the image to `shadows` and its highlights to
+    /// `highlights` (i.e. `duotone`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Color;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .duotone(Color::rgb(0, 0, 128), "gold".parse().unwrap())
+    ///     .duotone_alpha(75);
+    ///
+    /// let right = "https://example.domain.net/image.png?duotone=000080%2Cgold&duotone-alpha=75";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    pub fn duotone(mut self, shadows: Color, highlights: Color) -> Self {
+        self.set_param("duotone", format!("{},{}", shadows, highlights));
+        self
+    }
+
+    /// Set whether the colors of the image are inverted (i.e. `invert`).
+    pub fn invert(mut self, state: bool) -> Self {
+        self.set_param("invert", state.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_stylize_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .blur(2000)
+            .px(100)
+            .sepia(50)
+            .htn(0)
+            .monochrome(Color::rgb(16, 32, 64))
+            .invert(true);
+
+        let right = "https://test.domain.com/image.png?blur=2000&px=100&sepia=50&htn=0&monochrome=102040&invert=true";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_stylize_out_of_range() {
+        let results = [
+            Url::new(DOMAIN).try_blur(2001),
+            Url::new(DOMAIN).try_px(101),
+            Url::new(DOMAIN).try_sepia(101),
+            Url::new(DOMAIN).try_htn(101),
+            Url::new(DOMAIN).try_duotone_alpha(101),
+        ];
+
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Text overlay parameters (i.e. `txt`, `txt-font`, `txt-size`, `txt-color`,
+//! `txt-align`, `txt-pad`, `txt-shad`, `txt-fit`).
+use std::fmt::{self, Display};
+
+use super::{align, Align, Color};
+use crate::{validate, Result, Url};
+
+/// Controls how text is fit to the image (i.e. the `txt-fit` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextFit {
+    /// Shrink the text to fit the width of the image, less any padding.
+    Max,
+}
+
+impl Display for TextFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            TextFit::Max => "max",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A text overlay, comprised of the `txt` parameter and the `txt-*`
+/// parameters that style it.
+///
+/// A `TextOverlay` is attached to a `Url` with `Url::text()` and is
+/// expanded into its parameters, in a fixed order, when the `Url` is
+/// joined. Text that is not printable ASCII (i.e. emoji) is sent as the
+/// base64 `txt64` variant, see `TextOverlay::base64()`.
+///
+/// # Examples
+/// ```
+/// use imgix::params::{Align, TextOverlay};
+/// use imgix::Url;
+///
+/// let overlay = TextOverlay::new("Hello, World!")
+///     .font("avenir,bold")
+///     .size(48)
+///     .color("#fff".parse().unwrap())
+///     .align(&[Align::Bottom, Align::Center]);
+///
+/// let url = Url::new("example.domain.net")
+///     .path("image.png")
+///     .w(640)
+///     .text(overlay);
+///
+/// let right = "https://example.domain.net/image.png?w=640&txt=Hello%2C%20World!\
+///     &txt-font=avenir%2Cbold&txt-size=48&txt-color=fff&txt-align=bottom%2Ccenter";
+/// assert_eq!(url.join(), right);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextOverlay {
+    text: String,
+    base64: bool,
+    font: Option<String>,
+    size: Option<u32>,
+    color: Option<Color>,
+    align: Option<String>,
+    pad: Option<u32>,
+    shad: Option<f32>,
+    fit: Option<TextFit>,
+}
+
+impl TextOverlay {
+    /// Construct a new `TextOverlay` of the given `text`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `text` is an empty string.
+    pub fn new(text: &str) -> Self {
+        match TextOverlay::try_new(text) {
+            Ok(overlay) => overlay,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Construct a new `TextOverlay` of the given `text`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParamError` if `text` is an empty
+    /// string.
+    pub fn try_new(text: &str) -> Result<Self> {
+        validate::param_pair("txt", text)?;
+        Ok(TextOverlay {
+            text: String::from(text),
+            base64: false,
+            font: None,
+            size: None,
+            color: None,
+            align: None,
+            pad: None,
+            shad: None,
+            fit: None,
+        })
+    }
+
+    /// Set whether the text is _always_ sent as the base64 `txt64` variant.
+    /// Otherwise it is only sent as `txt64` if it is not printable ASCII.
+    pub fn base64(mut self, state: bool) -> Self {
+        self.base64 = state;
+        self
+    }
+
+    /// Set the font(s) of the text (i.e. `txt-font=avenir,bold`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `font` is an empty string.
+    pub fn font(self, font: &str) -> Self {
+        match self.try_font(font) {
+            Ok(overlay) => overlay,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the font(s) of the text (i.e. `txt-font=avenir,bold`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `font` is an empty
+    /// string.
+    pub fn try_font(mut self, font: &str) -> Result<Self> {
+        validate::param_pair("txt-font", font)?;
+        self.font = Some(String::from(font));
+        Ok(self)
+    }
+
+    /// Set the font size of the text (i.e. `txt-size`).
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the color of the text (i.e. `txt-color`).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the alignment of the text (i.e. `txt-align`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `align` is empty or if it contains more than
+    /// one vertical or more than one horizontal alignment.
+    pub fn align(self, align: &[Align]) -> Self {
+        match self.try_align(align) {
+            Ok(overlay) => overlay,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the alignment of the text (i.e. `txt-align`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `align` is empty or if
+    /// it contains more than one vertical or more than one horizontal
+    /// alignment.
+    pub fn try_align(mut self, align: &[Align]) -> Result<Self> {
+        self.align = Some(align::join("txt-align", align)?);
+        Ok(self)
+    }
+
+    /// Set the padding, in pixels, between the text and the edges of the
+    /// image (i.e. `txt-pad`).
+    pub fn pad(mut self, pad: u32) -> Self {
+        self.pad = Some(pad);
+        self
+    }
+
+    /// Set the strength of the text's drop shadow, from 0 to 10
+    /// (i.e. `txt-shad`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `shad` is out of range.
+    pub fn shad(self, shad: f32) -> Self {
+        match self.try_shad(shad) {
+            Ok(overlay) => overlay,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the strength of the text's drop shadow, from 0 to 10
+    /// (i.e. `txt-shad`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `shad` is out of range.
+    pub fn try_shad(mut self, shad: f32) -> Result<Self> {
+        validate::in_range("txt-shad", shad, 0.0..=10.0)?;
+        self.shad = Some(shad);
+        Ok(self)
+    }
+
+    /// Set how the text is fit to the image (i.e. `txt-fit`).
+    pub fn fit(mut self, fit: TextFit) -> Self {
+        self.fit = Some(fit);
+        self
+    }
+
+    /// Expand this overlay into its key-value parameters.
+    pub(crate) fn to_params(&self) -> Vec<(String, String)> {
+        let needs_base64 = self.base64 || self.text.chars().any(|c| !(' '..='~').contains(&c));
+        let txt = if needs_base64 { "txt64" } else { "txt" };
+
+        let mut params = vec![(txt.to_owned(), self.text.clone())];
+        let mut push = |k: &str, v: Option<String>| {
+            if let Some(v) = v {
+                params.push((k.to_owned(), v));
+            }
+        };
+
+        push("txt-font", self.font.clone());
+        push("txt-size", self.size.map(|v| v.to_string()));
+        push("txt-color", self.color.as_ref().map(Color::to_string));
+        push("txt-align", self.align.clone());
+        push("txt-pad", self.pad.map(|v| v.to_string()));
+        push("txt-shad", self.shad.map(|v| v.to_string()));
+        push("txt-fit", self.fit.map(|v| v.to_string()));
+        params
+    }
+}
+
+impl Url {
+    /// Attach a text overlay, replacing any overlay attached previously.
+    ///
+    /// The overlay's parameters follow this `Url`'s own parameters in the
+    /// query string, see `TextOverlay`. Any `txt*` parameters set before
+    /// are removed, and none can be set with `Url::param()` after.
+    pub fn text(mut self, overlay: TextOverlay) -> Self {
+        self.set_text(overlay);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    fn pairs(p: &[(&str, &str)]) -> Vec<(String, String)> {
+        p.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_text_params_order() {
+        let overlay = TextOverlay::new("Sale")
+            .fit(TextFit::Max)
+            .shad(2.5)
+            .pad(20)
+            .align(&[Align::Top, Align::Left])
+            .color(Color::argb(128, 255, 0, 0))
+            .size(64)
+            .font("georgia");
+
+        let right = pairs(&[
+            ("txt", "Sale"),
+            ("txt-font", "georgia"),
+            ("txt-size", "64"),
+            ("txt-color", "80ff0000"),
+            ("txt-align", "top,left"),
+            ("txt-pad", "20"),
+            ("txt-shad", "2.5"),
+            ("txt-fit", "max"),
+        ]);
+        assert_eq!(overlay.to_params(), right);
+    }
+
+    #[test]
+    fn test_text_base64() {
+        let overlay = TextOverlay::new("Hello 🌎 & Friends");
+        assert_eq!(overlay.to_params()[0].0, "txt64");
+
+        let overlay = TextOverlay::new("Hello & Friends");
+        assert_eq!(overlay.to_params()[0].0, "txt");
+
+        let overlay = TextOverlay::new("Hello & Friends").base64(true);
+        assert_eq!(overlay.to_params()[0].0, "txt64");
+
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .text(TextOverlay::new("Hello 🌎 & Friends").size(48));
+        let right =
+            "https://test.domain.com/image.png?txt64=SGVsbG8g8J-MjiAmIEZyaWVuZHM&txt-size=48";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_text_follows_params() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .text(TextOverlay::new("First"))
+            .w(320)
+            .text(TextOverlay::new("Second"));
+
+        let right = "https://test.domain.com/image.png?w=320&txt=Second";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_text_encode_disabled() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .param("ar", "16%3A9")
+            .text(TextOverlay::new("Héllo & you").font("avenir,bold"))
+            .encode(false);
+
+        let right = "https://test.domain.com/image.png?ar=16%3A9\
+            &txt64=SMOpbGxvICYgeW91&txt-font=avenir%2Cbold";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_text_replaces_params() {
+        let url: Url = "https://test.domain.com/image.png?txt=Old&w=10&txt-size=48"
+            .parse()
+            .unwrap();
+        let url = url.text(TextOverlay::new("New"));
+
+        let right = "https://test.domain.com/image.png?w=10&txt=New";
+        assert_eq!(url.join(), right);
+
+        // Nor can they be set again once an overlay is attached.
+        for k in ["txt", "txt64", "txt-size"].iter() {
+            assert!(matches!(
+                Url::new(DOMAIN)
+                    .text(TextOverlay::new("a"))
+                    .try_param(k, "b"),
+                Err(Error::ParamError(_))
+            ));
+        }
+        assert!(matches!(
+            Url::new(DOMAIN)
+                .text(TextOverlay::new("a"))
+                .try_params(&[("w", "10"), ("txt", "b")]),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_text_invalid() {
+        assert!(matches!(
+            TextOverlay::try_new(""),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            TextOverlay::new("a").try_font(""),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            TextOverlay::new("a").try_align(&[Align::Top, Align::Middle]),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            TextOverlay::new("a").try_shad(10.5),
+            Err(Error::RangeError(_))
+        ));
+    }
+}
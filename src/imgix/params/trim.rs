@@ -0,0 +1,264 @@
+//! Trim and fill parameters (i.e. `trim`, `trim-color`, `trim-md`,
+//! `trim-sd`, `trim-tol`, `fill`, `fill-color`).
+use std::fmt::{self, Display};
+
+use super::{value, Color};
+use crate::{validate, Error, Result, Url};
+
+/// How the edges of the image are trimmed (i.e. the `trim` parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trim {
+    /// Trim edges of a uniform color, see `Url::trim_md()` and
+    /// `Url::trim_sd()`.
+    Auto,
+    /// Trim edges of the `trim-color`, see `Url::trim_color()`.
+    Color,
+}
+
+impl Display for Trim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Trim::Auto => "auto",
+            Trim::Color => "color",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// How the space left around a resized image is filled (i.e. the `fill`
+/// parameter).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fill {
+    /// Fill with a blurred copy of the image.
+    Blur,
+    /// Fill with generated content that extends the image.
+    Gen,
+    /// Fill with the `fill-color`, see `Url::fill_color()`.
+    Solid,
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Fill::Blur => "blur",
+            Fill::Gen => "gen",
+            Fill::Solid => "solid",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl Url {
+    /// Set how the edges of the image are trimmed (i.e. `trim`).
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Trim;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .trim(Trim::Color)
+    ///     .trim_color("fff".parse().unwrap())
+    ///     .trim_tol(5.0);
+    /// assert_eq!(
+    ///     url.join(),
+    ///     "https://example.domain.net/image.png?trim=color&trim-color=fff&trim-tol=5"
+    /// );
+    ///
+    /// // `trim-color` is only used when `trim` is `Trim::Color`.
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .trim_color("fff".parse().unwrap());
+    /// assert!(url.validate().is_err());
+    /// ```
+    pub fn trim(mut self, trim: Trim) -> Self {
+        self.set_param("trim", trim.to_string());
+        self
+    }
+
+    /// Set the color of the edges trimmed when `trim` is `Trim::Color`
+    /// (i.e. `trim-color`).
+    pub fn trim_color(mut self, color: Color) -> Self {
+        self.set_param("trim-color", color.to_string());
+        self
+    }
+
+    /// Set the mean difference between pixels below which an edge is
+    /// trimmed when `trim` is `Trim::Auto` (i.e. `trim-md`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `md` is negative or not finite.
+    pub fn trim_md(self, md: f32) -> Self {
+        match self.try_trim_md(md) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the mean difference between pixels below which an edge is
+    /// trimmed when `trim` is `Trim::Auto` (i.e. `trim-md`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `md` is negative or
+    /// not finite.
+    pub fn try_trim_md(mut self, md: f32) -> Result<Self> {
+        validate::non_negative("trim-md", md)?;
+        self.set_param("trim-md", md.to_string());
+        Ok(self)
+    }
+
+    /// Set the standard deviation between pixels below which an edge is
+    /// trimmed when `trim` is `Trim::Auto` (i.e. `trim-sd`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `sd` is negative or not finite.
+    pub fn trim_sd(self, sd: f32) -> Self {
+        match self.try_trim_sd(sd) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the standard deviation between pixels below which an edge is
+    /// trimmed when `trim` is `Trim::Auto` (i.e. `trim-sd`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `sd` is negative or
+    /// not finite.
+    pub fn try_trim_sd(mut self, sd: f32) -> Result<Self> {
+        validate::non_negative("trim-sd", sd)?;
+        self.set_param("trim-sd", sd.to_string());
+        Ok(self)
+    }
+
+    /// Set how far, from 0 to 100, a pixel's color may differ from the
+    /// `trim-color` and still be trimmed (i.e. `trim-tol`).
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `tol` is out of range.
+    pub fn trim_tol(self, tol: f32) -> Self {
+        match self.try_trim_tol(tol) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set how far, from 0 to 100, a pixel's color may differ from the
+    /// `trim-color` and still be trimmed (i.e. `trim-tol`).
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::RangeError` if `tol` is out of range.
+    pub fn try_trim_tol(mut self, tol: f32) -> Result<Self> {
+        validate::in_range("trim-tol", tol, 0.0..=100.0)?;
+        self.set_param("trim-tol", tol.to_string());
+        Ok(self)
+    }
+
+    /// Set how the space left around a resized image is filled
+    /// (i.e. `fill`).
+    pub fn fill(mut self, fill: Fill) -> Self {
+        self.set_param("fill", fill.to_string());
+        self
+    }
+
+    /// Set the color used when `fill` is `Fill::Solid` (i.e. `fill-color`).
+    pub fn fill_color(mut self, color: Color) -> Self {
+        self.set_param("fill-color", color.to_string());
+        self
+    }
+}
+
+/// Check that `trim-color` and `trim-tol` are only used along with
+/// `trim=color` and that `fill-color` is only used along with `fill=solid`.
+pub(crate) fn check(params: &[(String, String)]) -> Result<()> {
+    let requires = [
+        ("trim-color", "trim", "color"),
+        ("trim-tol", "trim", "color"),
+        ("fill-color", "fill", "solid"),
+    ];
+
+    for (k, key, v) in requires.iter() {
+        if value(params, k).is_some() && value(params, key) != Some(v) {
+            return Err(Error::ParamError(format!(
+                "cannot use a `{}` without `{}={}`",
+                k, key, v
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOMAIN: &str = "test.domain.com";
+    const PATH: &str = "image.png";
+
+    #[test]
+    fn test_trim_auto() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .trim(Trim::Auto)
+            .trim_md(11.5)
+            .trim_sd(0.0);
+
+        let right = "https://test.domain.com/image.png?trim=auto&trim-md=11.5&trim-sd=0";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_fill() {
+        let url = Url::new(DOMAIN)
+            .path(PATH)
+            .fill_color(Color::rgb(0, 0, 0))
+            .fill(Fill::Solid);
+
+        let right = "https://test.domain.com/image.png?fill-color=000000&fill=solid";
+        assert_eq!(url.join(), right);
+
+        let url = Url::new(DOMAIN).path(PATH).fill(Fill::Blur);
+        assert_eq!(url.join(), "https://test.domain.com/image.png?fill=blur");
+    }
+
+    #[test]
+    fn test_trim_out_of_range() {
+        let results = [
+            Url::new(DOMAIN).try_trim_md(-1.0),
+            Url::new(DOMAIN).try_trim_md(f32::INFINITY),
+            Url::new(DOMAIN).try_trim_sd(f32::NAN),
+            Url::new(DOMAIN).try_trim_tol(100.5),
+        ];
+
+        for result in results.iter() {
+            assert!(matches!(result, Err(Error::RangeError(_))));
+        }
+    }
+
+    #[test]
+    fn test_trim_fill_combinations() {
+        let invalid = [
+            Url::new(DOMAIN).path(PATH).trim_tol(10.0),
+            Url::new(DOMAIN)
+                .path(PATH)
+                .trim(Trim::Auto)
+                .trim_color(Color::rgb(255, 255, 255)),
+            Url::new(DOMAIN)
+                .path(PATH)
+                .fill(Fill::Gen)
+                .fill_color(Color::rgb(255, 255, 255)),
+        ];
+
+        for url in invalid.iter() {
+            assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+        }
+    }
+}
@@ -33,12 +33,19 @@ use crate::url::{Scheme, Url};
 /// https://html.spec.whatwg.org/multipage/urls-and-fetching.html#valid-non-empty-url
 /// [Srcset attributes specification]:
 /// (https://html.spec.whatwg.org/multipage/images.html#srcset-attributes)
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SourceSet {
+    // Populated by `From<Url>`, but not read until art direction
+    // (i.e. `sizes` and `media`) is supported.
+    #[allow(dead_code)]
     src: Option<Url>,
+    #[allow(dead_code)]
     action: Option<Action>,
+    #[allow(dead_code)]
     srcset: Option<Vec<String>>,
+    #[allow(dead_code)]
     sizes: Option<Vec<String>>,
+    #[allow(dead_code)]
     media: Option<String>,
     config: Config,
 }
@@ -69,14 +76,27 @@ impl SourceSet {
         }
     }
 
-    // TODO: consider `pub struct Params` where `impl From<&[....]> for Params`...
-    pub fn params(self, params: &'static [(&'static str, &'static str)]) -> Self {
+    pub fn params<K: AsRef<str>, V: AsRef<str>>(self, params: &[(K, V)]) -> Self {
         SourceSet {
             config: self.config.set_params(params),
             ..self
         }
     }
 
+    pub fn lib(self, l: &str) -> Self {
+        SourceSet {
+            config: self.config.set_lib(l),
+            ..self
+        }
+    }
+
+    pub fn token(self, t: &str) -> Self {
+        SourceSet {
+            config: self.config.set_token(t),
+            ..self
+        }
+    }
+
     pub fn ratios(self, ratios: &'static [u32; 5]) -> Self {
         SourceSet {
             config: self.config.set_ratios(ratios),
@@ -96,7 +116,7 @@ impl SourceSet {
     }
 
     pub fn get_targets(&self) -> &[u32] {
-        &self.config.get_targets()
+        self.config.get_targets()
     }
 
     pub fn variable_quality(self, state: bool) -> Self {
@@ -129,8 +149,8 @@ impl SourceSet {
         let action = Self::infer_action(&url);
 
         match action {
-            Action::PixelDensity => Self::build_pixel_set(&self, &url, &action),
-            Action::Viewport => Self::build_viewport_set(&self, &url, &action),
+            Action::PixelDensity => Self::build_pixel_set(self, &url, &action),
+            Action::Viewport => Self::build_viewport_set(self, &url, &action),
             _ => unimplemented!(),
         }
     }
@@ -158,32 +178,19 @@ impl SourceSet {
             return Action::PixelDensity;
         }
 
-        return Action::Viewport;
+        Action::Viewport
     }
 
     fn build_pixel_set(&self, url: &Url, action: &Action) -> Vec<String> {
         if self.uses_variable_quality() {
-            create_variable_quality_set(&url, self.get_ratios(), &action, self.get_qualities())
+            create_variable_quality_set(url, self.get_ratios(), action, self.get_qualities())
         } else {
-            create_srcset(&url, self.get_ratios(), &action)
+            create_srcset(url, self.get_ratios(), action)
         }
     }
 
     fn build_viewport_set(&self, url: &Url, action: &Action) -> Vec<String> {
-        create_srcset(&url, self.get_targets(), &action)
-    }
-}
-
-impl Default for SourceSet {
-    fn default() -> Self {
-        SourceSet {
-            src: None,
-            action: None,
-            srcset: None,
-            sizes: None,
-            media: None,
-            config: Config::default(),
-        }
+        create_srcset(url, self.get_targets(), action)
     }
 }
 
@@ -217,9 +224,9 @@ fn create_srcset(url: &Url, targets: &[u32], action: &Action) -> Vec<String> {
     let mut srcset = Vec::new();
 
     for t in targets {
-        srcset.push(candidate(&url, &t.to_string(), &action));
+        srcset.push(candidate(url, &t.to_string(), action));
     }
-    return srcset;
+    srcset
 }
 
 fn create_variable_quality_set(
@@ -231,63 +238,47 @@ fn create_variable_quality_set(
     let mut srcset = Vec::new();
 
     for (r, q) in ratios.iter().zip(qualities) {
-        let more = format!("&q={quality}", quality = q);
-        srcset.push(candidate_and(&url, &r.to_string(), action, &more));
-    }
-    return srcset;
+        let quality = q.to_string();
+        srcset.push(candidate_and(
+            url,
+            &r.to_string(),
+            action,
+            &[("q", &quality)],
+        ));
+    }
+    srcset
 }
 
 fn candidate(url: &Url, value: &str, action: &Action) -> String {
-    let (descriptor, key) = match action {
-        Action::Viewport => ("w", "w"),
-        Action::PixelDensity => ("x", "dpr"),
-        Action::ArtDirection => unimplemented!(),
-    };
-
-    let param = if url.has_params() {
-        format!("&{key}={value}", key = key, value = value)
-    } else {
-        format!("?{key}={value}", key = key, value = value)
-    };
-
-    format!(
-        "{url}{param} {value}{descriptor}",
-        url = url.join(),
-        param = param,
-        value = value,
-        descriptor = descriptor
-    )
+    candidate_and(url, value, action, &[])
 }
 
-fn candidate_and(url: &Url, value: &str, action: &Action, more: &str) -> String {
+fn candidate_and(url: &Url, value: &str, action: &Action, more: &[(&str, &str)]) -> String {
     let (descriptor, key) = match action {
         Action::Viewport => ("w", "w"),
         Action::PixelDensity => ("x", "dpr"),
         Action::ArtDirection => unimplemented!(),
     };
 
-    let param = if url.has_params() {
-        format!("&{key}={value}", key = key, value = value)
-    } else {
-        format!("?{key}={value}", key = key, value = value)
-    };
+    // The candidate's parameters are joined _through_ the `Url` so that
+    // a signed `Url` yields signed candidates.
+    let mut extra = more.to_vec();
+    extra.push((key, value));
 
     format!(
-        "{url}{more}{param} {value}{descriptor}",
-        url = url.join(),
-        param = param,
-        more = more,
+        "{url} {value}{descriptor}",
+        url = url.join_with(&extra),
         value = value,
         descriptor = descriptor
     )
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Config {
     scheme: Option<Scheme>,
     domain: Option<String>,
     path: Option<String>,
-    params: Option<&'static [(&'static str, &'static str)]>,
+    params: Option<Vec<(String, String)>>,
     lib: Option<String>,
     token: Option<String>,
     targets: Option<&'static [u32]>,
@@ -296,23 +287,6 @@ pub struct Config {
     use_variable_quality: Option<bool>,
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Config {
-            scheme: None,
-            domain: None,
-            path: None,
-            params: None,
-            lib: None,
-            token: None,
-            targets: None,
-            ratios: None,
-            qualities: None,
-            use_variable_quality: None,
-        }
-    }
-}
-
 impl Config {
     fn set_scheme(self, s: Scheme) -> Self {
         Config {
@@ -335,7 +309,12 @@ impl Config {
         }
     }
 
-    fn set_params(self, params: &'static [(&'static str, &'static str)]) -> Self {
+    fn set_params<K: AsRef<str>, V: AsRef<str>>(self, params: &[(K, V)]) -> Self {
+        let params = params
+            .iter()
+            .map(|(k, v)| (String::from(k.as_ref()), String::from(v.as_ref())))
+            .collect();
+
         Config {
             params: Some(params),
             ..self
@@ -350,7 +329,7 @@ impl Config {
     }
 
     fn get_ratios(&self) -> &[u32; 5] {
-        &self.ratios.unwrap_or(&TARGET_RATIOS)
+        self.ratios.unwrap_or(&TARGET_RATIOS)
     }
 
     fn set_targets(self, targets: &'static [u32]) -> Self {
@@ -361,7 +340,7 @@ impl Config {
     }
 
     fn get_targets(&self) -> &[u32] {
-        &self.targets.unwrap_or(&TARGET_WIDTHS[..])
+        self.targets.unwrap_or(&TARGET_WIDTHS[..])
     }
 
     fn set_qualities(self, qualities: &'static [u32; 5]) -> Self {
@@ -372,7 +351,7 @@ impl Config {
     }
 
     fn get_qualities(&self) -> &[u32] {
-        &self.qualities.unwrap_or(&DPR_QUALITIES[..])
+        self.qualities.unwrap_or(&DPR_QUALITIES[..])
     }
 
     fn set_use_variable_quality(self, state: bool) -> Self {
@@ -386,13 +365,42 @@ impl Config {
         self.use_variable_quality.unwrap_or(true)
     }
 
+    fn set_lib(self, l: &str) -> Self {
+        Config {
+            lib: Some(String::from(l)),
+            ..self
+        }
+    }
+
+    fn set_token(self, t: &str) -> Self {
+        Config {
+            token: Some(String::from(t)),
+            ..self
+        }
+    }
+
     fn to_url(&self) -> Url {
         let msg = "neither `domain` nor `path` can be `None`";
         match (&self.domain, &self.path) {
-            (None, None) | (None, _) | (_, None) => panic!(msg),
-            (Some(domain), Some(path)) => Url::new(&domain)
-                .path(&path)
-                .params(self.params.unwrap_or_default()),
+            (None, None) | (None, _) | (_, None) => panic!("{}", msg),
+            (Some(domain), Some(path)) => {
+                let mut url = Url::new(domain)
+                    .path(path)
+                    .params(self.params.as_deref().unwrap_or_default());
+
+                if let Some(scheme) = self.scheme {
+                    url = url.scheme(scheme);
+                }
+
+                if let Some(ref lib) = self.lib {
+                    url = url.lib(lib);
+                }
+
+                if let Some(ref token) = self.token {
+                    url = url.token(token);
+                }
+                url
+            }
         }
     }
 }
@@ -421,10 +429,9 @@ mod test {
 
     #[test]
     fn test_from_url_dpr_ar_h() {
-        // TODO: encode!!!
         let url = Url::new("test.imgix.net")
             .path("image.png")
-            .params(&[("ar", "4%3A3"), ("h", "320")]);
+            .params(&[("ar", "4:3"), ("h", "320")]);
 
         let left = Some(vec![
             "https://test.imgix.net/image.png?ar=4%3A3&h=320&dpr=1 1x".to_owned(),
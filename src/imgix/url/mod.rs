@@ -1,7 +1,11 @@
 // Rust Standard Library Imports.
+use std::convert::TryFrom;
 use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use super::{constants, validate, Error};
+use super::params::{self, TextOverlay, Watermark};
+use super::{constants, encode, validate, Clock, Error, Result, SystemClock};
 
 /// Primary structure used to generate imgix URLs.
 ///
@@ -26,6 +30,10 @@ use super::{constants, validate, Error};
 /// can panic. They panic to try to ensure invalid urls are
 /// never constructed. This is to provide higher-level structures
 /// certain guarantees about the representation of a `Url`.
+///
+/// Every building function that can panic has a `try_*` counterpart
+/// (i.e. `try_path`, `try_join`) that returns an `imgix::Result` instead.
+/// Use these when handling untrusted input, i.e. user-provided paths.
 #[derive(Debug)]
 pub struct Url {
     /// The scheme component of a URL, i.e. https, http, etc.
@@ -55,9 +63,35 @@ pub struct Url {
     /// has specified. Therefore, the order in which parameters are listed
     /// is the same order they will appear in the generated `Url`'s query
     /// string.
-    params: Vec<(&'static str, &'static str)>,
+    ///
+    /// Keys and values are owned so that parameters can be computed at
+    /// runtime (i.e. widths, crop rectangles, or values read from a database).
+    params: Vec<(String, String)>,
+    /// An optional blend, either a color or the nested URL of an image,
+    /// joined as the `blend` parameter after `params`.
+    blend: Option<String>,
+    /// An optional text overlay, expanded into its `txt*` parameters
+    /// after `params` and `blend` when the `Url` is joined.
+    text: Option<TextOverlay>,
+    /// An optional watermark, expanded into its `mark*` parameters
+    /// after `text` when the `Url` is joined.
+    mark: Option<Watermark>,
     /// Optional signing token used to sign URLs.
     token: Option<String>,
+    /// The signature of a `Url` that has been parsed from a string. It is
+    /// carried over, as-is, when the `Url` is joined without a `token` and
+    /// is discarded as soon as the `Url` is modified.
+    signature: Option<String>,
+    /// The path and query of a signed `Url` that has been parsed from a
+    /// string which `join()` would not reproduce byte-for-byte (i.e. one
+    /// with `ixlib` last, or with unencoded values). It is carried over,
+    /// as-is, along with the `signature` so that the signature still
+    /// matches, and is discarded with it.
+    raw: Option<String>,
+    /// Whether the `path` and `params` are percent-encoded when joined.
+    /// This is `true` by default and should only be turned off if the
+    /// `path` and `params` have _already_ been encoded.
+    encode: bool,
 }
 
 impl Default for Url {
@@ -70,8 +104,14 @@ impl Default for Url {
             domain: "".to_owned(),
             lib: "".to_owned(),
             params: vec![],
+            blend: None,
             path: None,
+            text: None,
+            mark: None,
             token: None,
+            signature: None,
+            raw: None,
+            encode: true,
         }
     }
 }
@@ -82,71 +122,151 @@ impl Url {
     /// # Panics
     ///
     /// This constructor will fail if the `domain` is an empty string.
+    /// See `Url::try_new()` for the non-panicking alternative.
     pub fn new(domain: &str) -> Self {
-        match validate::domain(&domain) {
-            Ok(()) => Url {
-                domain: String::from(domain),
-                ..Default::default()
-            },
+        match Self::try_new(domain) {
+            Ok(url) => url,
             Err(e) => panic!("{}", e),
         }
     }
 
+    /// Construct a new `Url` given a domain.
+    ///
+    /// # Errors
+    ///
+    /// This constructor returns an `Error::DomainError` if the `domain` is
+    /// an empty string.
+    pub fn try_new(domain: &str) -> Result<Self> {
+        validate::domain(domain)?;
+        Ok(Url {
+            domain: String::from(domain),
+            ..Default::default()
+        })
+    }
+
     /// Set the domain value (i.e. "example.domain.net").
     ///
     /// # Panics
     ///
     /// This method panics if passed an empty string.
-    pub fn domain(mut self, d: &str) -> Self {
-        match validate::domain(&d) {
-            Ok(()) => {
-                self.domain = String::from(d);
-                self
-            }
+    pub fn domain(self, d: &str) -> Self {
+        match self.try_domain(d) {
+            Ok(url) => url,
             Err(e) => panic!("{}", e),
         }
     }
 
+    /// Set the domain value (i.e. "example.domain.net").
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::DomainError` if passed an empty string.
+    pub fn try_domain(mut self, d: &str) -> Result<Self> {
+        validate::domain(d)?;
+        self.domain = String::from(d);
+        Ok(self)
+    }
+
     /// Set the path value to the image file (i.e. 'image/path.png').
     ///
+    /// If the path is a fully-qualified URL (i.e. 'https://example.com/image.png')
+    /// the `Url` is treated as a Web Proxy `Url`, see `PathKind::WebProxy`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::{PathKind, Url};
+    ///
+    /// let url = Url::new("my-social-network.imgix.net")
+    ///     .path("http://avatars.com/john-smith.png")
+    ///     .token("FOO123bar");
+    ///
+    /// let right = "https://my-social-network.imgix.net/http%3A%2F%2Favatars.com%2Fjohn-smith.png?s=493a52f008c91416351f8b33d4883135";
+    /// assert_eq!(url.get_path_kind(), PathKind::WebProxy);
+    /// assert_eq!(url.join(), right);
+    /// ```
+    ///
     /// # Panics
     ///
     /// This method panics if passed an empty string.
-    pub fn path(mut self, p: &str) -> Self {
-        match validate::path(&p) {
-            Ok(()) => {
-                self.path = Some(String::from(p));
-                self
-            }
+    pub fn path(self, p: &str) -> Self {
+        match self.try_path(p) {
+            Ok(url) => url,
             Err(e) => panic!("{}", e),
         }
     }
 
+    /// Set the path value to the image file (i.e. 'image/path.png').
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::{Error, Url};
+    ///
+    /// // i.e. a path supplied by a user.
+    /// let path = "";
+    /// let url = Url::new("example.domain.net").try_path(path);
+    ///
+    /// match url {
+    ///     Ok(url) => println!("{}", url.join()),
+    ///     Err(Error::PathError(msg)) => println!("400 Bad Request: {}", msg),
+    ///     Err(e) => panic!("{}", e),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::PathError` if passed an empty string.
+    pub fn try_path(mut self, p: &str) -> Result<Self> {
+        validate::path(p)?;
+        self.path = Some(String::from(p));
+        self.discard_signature();
+        Ok(self)
+    }
+
     /// Set an arbitrary key-value parameter (i.e. k='w', v='100'
     /// or k='fit', v='crop').
     ///
+    /// The value of a base64 parameter variant, one whose key ends in "64"
+    /// (i.e. `txt64`, `mark64`), is base64 encoded when the `Url` is joined.
+    /// This is the most robust way to pass arbitrary UTF-8 text.
+    ///
     /// # Examples
     /// ```
     /// use imgix::Url;
     /// let url = Url::new("example.domain.net").param("w", "320").path("test").lib("");
     /// let right = "https://example.domain.net/test?w=320";
-    /// assert_eq!(url.join(), "https://example.domain.net/test?w=320")
+    /// assert_eq!(url.join(), "https://example.domain.net/test?w=320");
+    ///
+    /// let url = Url::new("example.domain.net").path("test").param("txt64", "Hello, World!");
+    /// assert_eq!(url.join(), "https://example.domain.net/test?txt64=SGVsbG8sIFdvcmxkIQ");
     /// ```
     ///
     /// # Panics
     ///
-    /// This method panics if any key `k` or any value `v` is an empty string,
-    /// where `k` and `v` represent string literals.
-    pub fn param(mut self, k: &'static str, v: &'static str) -> Self {
-        match validate::param_pair(&k, &v) {
-            Ok(()) => {
-                self.params.push((k, v));
-                self
-            }
+    /// This method panics if any key `k` or any value `v` is an empty string.
+    pub fn param(self, k: &str, v: &str) -> Self {
+        match self.try_param(k, v) {
+            Ok(url) => url,
             Err(e) => panic!("{}", e),
         }
     }
 
+    /// Set an arbitrary key-value parameter (i.e. k='w', v='100'
+    /// or k='fit', v='crop').
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if any key `k` or any
+    /// value `v` is an empty string, or if `k` is set by a typed value
+    /// attached to this `Url` (i.e. `blend`, see `Url::blend_color()`,
+    /// `txt*`, see `Url::text()`, or `mark*`, see `Url::mark()`).
+    pub fn try_param(mut self, k: &str, v: &str) -> Result<Self> {
+        validate::param_pair(k, v)?;
+        self.check_attached(k)?;
+        self.params.push((String::from(k), String::from(v)));
+        self.discard_signature();
+        Ok(self)
+    }
+
     /// Set an arbitrary number of key-value parameters.
     ///
     /// # Examples
@@ -159,19 +279,45 @@ impl Url {
     ///
     /// let right = "https://example.domain.net/test?w=320&h=640&fit=crop";
     /// assert_eq!(url.join(), right);
+    ///
+    /// // Parameters can also be computed at runtime.
+    /// let widths: Vec<(String, String)> = vec![("w".to_owned(), (160 * 2).to_string())];
+    /// let url = Url::new("example.domain.net").path("test").params(&widths);
+    /// assert_eq!(url.join(), "https://example.domain.net/test?w=320");
     /// ```
     ///
     /// # Panics
     ///
     /// This method panics if any key `k` or any value `v` is an empty string.
-    pub fn params(mut self, p: &[(&'static str, &'static str)]) -> Self {
+    pub fn params<K: AsRef<str>, V: AsRef<str>>(self, p: &[(K, V)]) -> Self {
+        match self.try_params(p) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set an arbitrary number of key-value parameters.
+    ///
+    /// Either all of the parameters are set or, if any pair is invalid,
+    /// none of them are.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if any key `k` or any
+    /// value `v` is an empty string, or if `k` is set by a typed value
+    /// attached to this `Url`, see `Url::try_param()`.
+    pub fn try_params<K: AsRef<str>, V: AsRef<str>>(mut self, p: &[(K, V)]) -> Result<Self> {
         for (k, v) in p.iter() {
-            match validate::param_pair(&k, &v) {
-                Ok(()) => self.params.push((k, v)),
-                Err(e) => panic!("{}", e),
-            }
+            validate::param_pair(k.as_ref(), v.as_ref())?;
+            self.check_attached(k.as_ref())?;
         }
-        self
+
+        for (k, v) in p.iter() {
+            self.params
+                .push((String::from(k.as_ref()), String::from(v.as_ref())));
+        }
+        self.discard_signature();
+        Ok(self)
     }
 
     /// Set the library version explicitly, see `Url::ix()` for the
@@ -209,11 +355,28 @@ impl Url {
     /// ```
     pub fn lib(mut self, l: &str) -> Self {
         self.lib = String::from(l);
+        self.discard_signature();
         self
     }
 
     /// Set the signing token.
-    /// TODO: Test token post md5 implementation.
+    ///
+    /// When a token is present, `join()` signs the `Url` by appending an
+    /// `s` parameter. The signature is the hex-encoded MD5 digest of the
+    /// token followed by the path and query string (e.g. `/image.png?w=320`),
+    /// exactly as they appear in the joined `Url`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("my-social-network.imgix.net")
+    ///     .path("users/1.png")
+    ///     .token("FOO123bar");
+    ///
+    /// let right = "https://my-social-network.imgix.net/users/1.png?s=6797c24146142d5b40bde3141fd3600c";
+    /// assert_eq!(url.join(), right);
+    /// ```
     pub fn token(mut self, t: &str) -> Self {
         self.token = Some(String::from(t));
         self
@@ -225,6 +388,150 @@ impl Url {
         self
     }
 
+    /// Set whether the `path` and `params` are percent-encoded when the
+    /// `Url` is joined (`true` by default).
+    ///
+    /// Paths are encoded segment-by-segment, while parameter keys and
+    /// values are encoded as URI components. Turn encoding off when the
+    /// `path` and `params` have already been encoded, otherwise they will
+    /// be encoded twice.
+    ///
+    /// The values of base64 parameter variants (i.e. `txt64`), the `blend`,
+    /// and the parameters of a `TextOverlay` or a `Watermark` are encoded
+    /// either way.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("my image.png")
+    ///     .param("ar", "16:9");
+    ///
+    /// let right = "https://example.domain.net/my%20image.png?ar=16%3A9";
+    /// assert_eq!(url.join(), right);
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("my%20image.png")
+    ///     .param("ar", "16%3A9")
+    ///     .encode(false);
+    ///
+    /// assert_eq!(url.join(), right);
+    /// ```
+    pub fn encode(mut self, state: bool) -> Self {
+        self.encode = state;
+        self.discard_signature();
+        self
+    }
+
+    /// Set the time at which this `Url` expires (i.e. the `expires`
+    /// parameter). Requests made after this time will fail.
+    ///
+    /// The `expires` parameter is part of the signed query, so an expiring
+    /// `Url` must be signed, see `Url::token()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .expires(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+    ///     .token("FOO123bar");
+    ///
+    /// assert!(url.join().contains("?expires=1600000000&s="));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `at` is earlier than the Unix epoch.
+    pub fn expires(self, at: SystemTime) -> Self {
+        match self.try_expires(at) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set the time at which this `Url` expires, see `Url::expires()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if `at` is earlier than
+    /// the Unix epoch.
+    pub fn try_expires(mut self, at: SystemTime) -> Result<Self> {
+        let timestamp = at
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::ParamError("`expires` cannot precede the Unix epoch".to_owned()))?;
+
+        self.set_param("expires", timestamp.as_secs().to_string());
+        Ok(self)
+    }
+
+    /// Set this `Url` to expire after the duration `d` has elapsed, as
+    /// measured by the system's clock. See `Url::expires_in_with()`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the time at which this `Url` expires cannot
+    /// be represented.
+    pub fn expires_in(self, d: Duration) -> Self {
+        self.expires_in_with(d, &SystemClock)
+    }
+
+    /// Set this `Url` to expire after the duration `d` has elapsed, as
+    /// measured by the system's clock, see `Url::expires_in()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if the time at which this
+    /// `Url` expires cannot be represented.
+    pub fn try_expires_in(self, d: Duration) -> Result<Self> {
+        self.try_expires_in_with(d, &SystemClock)
+    }
+
+    /// Set this `Url` to expire after the duration `d` has elapsed, as
+    /// measured by `clock`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use imgix::Url;
+    ///
+    /// let now = || UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .expires_in_with(Duration::from_secs(3600), &now);
+    ///
+    /// assert_eq!(url.get_params(), &[("expires".to_owned(), "1600003600".to_owned())]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the time at which this `Url` expires cannot
+    /// be represented (i.e. if `d` is `Duration::MAX`).
+    pub fn expires_in_with<C: Clock>(self, d: Duration, clock: &C) -> Self {
+        match self.try_expires_in_with(d, clock) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Set this `Url` to expire after the duration `d` has elapsed, as
+    /// measured by `clock`, see `Url::expires_in_with()`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` if the time at which this
+    /// `Url` expires cannot be represented.
+    pub fn try_expires_in_with<C: Clock>(self, d: Duration, clock: &C) -> Result<Self> {
+        let at = clock
+            .now()
+            .checked_add(d)
+            .ok_or_else(|| Error::ParamError(format!("`expires` cannot be {:?} from now", d)))?;
+        self.try_expires(at)
+    }
+
     // Set the library version to this crate's current `lib_version()`.
     // In the official imgix docs the `ixlib` parameter is used for
     // _diagnostic purposes_. It helps us help our users and customers,
@@ -232,6 +539,7 @@ impl Url {
     // What you see is what you get (WYSIWYG).
     pub fn ix(mut self) -> Self {
         self.lib = constants::lib_version();
+        self.discard_signature();
         self
     }
 
@@ -240,6 +548,9 @@ impl Url {
     ///
     /// {scheme}://{domain}/{path}?{lib}{query}
     ///
+    /// If a signing `token` has been set, the signature is appended as the
+    /// final `s` parameter, see `Url::token()`.
+    ///
     /// This function will only `join` the components of a `Url` if a `path`
     /// has been specified.
     ///
@@ -247,56 +558,179 @@ impl Url {
     ///
     /// This function will panic if the image `path` has not been specified.
     /// (i.e. if the `path` is `None`). This is to ensure that a `Url` is
-    /// joined if it is in a _valid_ state.
+    /// joined if it is in a _valid_ state. It will also panic if the `path`
+    /// is a Web Proxy path, or if the `Url` expires, and no signing `token`
+    /// has been set. See `Url::try_join()` for the non-panicking alternative.
+    ///
+    /// Parameters are joined as they are, even if they are combined in a way
+    /// imgix does not support, see `Url::validate()`.
     pub fn join(&self) -> String {
+        self.join_with(&[])
+    }
+
+    /// Join the components of a `Url`, see `Url::join()`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::JoinError` if the image `path` has
+    /// not been specified or if the `path` is a Web Proxy path, or the `Url`
+    /// expires, and no signing `token` has been set.
+    pub fn try_join(&self) -> Result<String> {
+        self.try_join_with(&[])
+    }
+
+    /// Check that the parameters of this `Url` are combined in a way imgix
+    /// supports (i.e. `corner-radius` only along with `mask=corners`).
+    ///
+    /// Typed parameters can be set in any order, so their combinations are
+    /// not checked as they are set. Nor are they checked when the `Url` is
+    /// joined, so that untyped and parsed parameters are joined as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::params::Mask;
+    /// use imgix::Url;
+    ///
+    /// let url = Url::new("example.domain.net")
+    ///     .path("image.png")
+    ///     .corner_radius(16);
+    /// assert!(url.validate().is_err());
+    ///
+    /// let url = url.mask(Mask::Corners);
+    /// assert!(url.validate().is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method returns an `Error::ParamError` describing the first
+    /// unsupported combination found.
+    pub fn validate(&self) -> Result<()> {
+        params::check(&self.params)
+    }
+
+    /// Join the components of a `Url` as `join()` does, appending the
+    /// `extra` parameters after this `Url`'s own parameters.
+    ///
+    /// This lets crate internals (e.g. source sets) derive variations of a
+    /// `Url` whose signature still covers every parameter in the query.
+    ///
+    /// # Panics
+    ///
+    /// This function panics under the same conditions as `Url::join()`.
+    pub(crate) fn join_with(&self, extra: &[(&str, &str)]) -> String {
+        match self.try_join_with(extra) {
+            Ok(url) => url,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// The fallible counterpart to `Url::join_with()`.
+    fn try_join_with(&self, extra: &[(&str, &str)]) -> Result<String> {
         // Join this url, only-if a `path` has been specified.
-        match self.path {
-            Some(ref path) => {
-                let query = Self::join_params(&self.params);
-                // If we make it here then the following is true:
-                // * a path has been assigned and is not `None`
-                // * a query string was generated successfully and
-                //   is either empty or non-empty.
-                match (&self.lib.is_empty(), &query.is_empty()) {
-                    // All present, no empty fields, construct full url.
-                    (false, false) => format!(
-                        "{scheme}://{domain}/{path}?{lib}&{query}",
-                        scheme = self.scheme,
-                        domain = self.domain,
-                        path = path,
-                        lib = self.lib,
-                        query = query,
-                    ),
-                    // Query string is empty, but lib is non-empty.
-                    (false, true) => format!(
-                        "{scheme}://{domain}/{path}?{lib}",
-                        scheme = self.scheme,
-                        domain = self.domain,
-                        lib = self.lib,
-                        path = path,
-                    ),
-                    // Lib is empty, but query is non-empty.
-                    (true, false) => format!(
-                        "{scheme}://{domain}/{path}?{query}",
-                        scheme = self.scheme,
-                        domain = self.domain,
-                        path = path,
-                        query = query
-                    ),
-                    // Both lib and query strings are empty.
-                    (true, true) => format!(
-                        "{scheme}://{domain}/{path}",
-                        scheme = self.scheme,
-                        domain = self.domain,
-                        path = path,
-                    ),
-                }
+        let path = match self.path {
+            Some(ref path) => path,
+            None => {
+                return Err(Error::JoinError(
+                    "cannot `join` when `path` is `None`".to_owned(),
+                ))
             }
-            None => panic!(
-                "{}",
-                Error::JoinError("cannot `join` when `path` is `None`".to_owned())
-            ),
+        };
+
+        let kind = PathKind::of(path);
+
+        // Web Proxy sources _must_ be signed.
+        if kind == PathKind::WebProxy && self.token.is_none() && self.signature.is_none() {
+            return Err(Error::JoinError(
+                "cannot `join` a web proxy `path` without a `token`".to_owned(),
+            ));
+        }
+
+        // Signing is required since an unsigned `expires` parameter could
+        // simply be removed from the `Url` to extend its lifetime.
+        let expires = self.params.iter().any(|(k, _)| k == "expires");
+        if expires && self.token.is_none() && self.signature.is_none() {
+            return Err(Error::JoinError(
+                "cannot `join` an expiring `Url` without a `token`".to_owned(),
+            ));
         }
+
+        // An unmodified, parsed `Url` is joined as it was parsed, so that its
+        // signature still matches.
+        if let (None, Some(raw), true) = (&self.token, &self.raw, extra.is_empty()) {
+            return Ok(format!(
+                "{scheme}://{domain}{raw}",
+                scheme = self.scheme,
+                domain = self.domain,
+                raw = raw
+            ));
+        }
+
+        // A Web Proxy path is encoded as a whole, including its
+        // scheme and separators.
+        let path = match (kind, self.encode) {
+            (PathKind::Relative, true) => format!("/{}", encode::path(path)),
+            (PathKind::WebProxy, true) => format!("/{}", encode::component(path)),
+            (_, false) => format!("/{}", path),
+        };
+        let params = self.join_query_params(&self.params);
+        // Blends and overlays always hold raw values (i.e. nested URLs), so
+        // they are encoded regardless of `encode`.
+        let blend = match self.blend {
+            Some(ref blend) => Self::join_encoded_params(&[("blend", blend)]),
+            None => String::new(),
+        };
+        let text = match self.text {
+            Some(ref text) => Self::join_encoded_params(&text.to_params()),
+            None => String::new(),
+        };
+        let mark = match self.mark {
+            Some(ref mark) => Self::join_encoded_params(&mark.to_params()),
+            None => String::new(),
+        };
+        let extra = self.join_query_params(extra);
+
+        // The query string has the form:
+        // {lib}&{params}&{blend}&{text}&{mark}&{extra},
+        // where any empty component is omitted.
+        let query = [
+            self.lib.as_str(),
+            params.as_str(),
+            blend.as_str(),
+            text.as_str(),
+            mark.as_str(),
+            extra.as_str(),
+        ]
+        .iter()
+        .filter(|component| !component.is_empty())
+        .cloned()
+        .collect::<Vec<&str>>()
+        .join("&");
+
+        let mut url = format!(
+            "{scheme}://{domain}{path}",
+            scheme = self.scheme,
+            domain = self.domain,
+            path = path,
+        );
+
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&query);
+        }
+
+        // The signature must always be the last parameter.
+        let signature = match (&self.token, &self.signature) {
+            (Some(token), _) => Some(sign(token, &path, &query)),
+            (None, Some(signature)) => Some(signature.clone()),
+            (None, None) => None,
+        };
+
+        if let Some(signature) = signature {
+            url.push(if query.is_empty() { '?' } else { '&' });
+            url.push_str("s=");
+            url.push_str(&signature);
+        }
+        Ok(url)
     }
 
     /// Join a list of key-value parameter pairs.
@@ -308,36 +742,153 @@ impl Url {
     ///
     /// # Examples
     ///
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let query = Url::join_params(&[("w", "320"), ("h", "640")]);
+    /// assert_eq!(query, "w=320&h=640");
+    /// ```
+    ///
     /// # Panics
     ///
     /// This function panics if any key `k` or any value `v` is an empty string.
-    pub fn join_params(p: &[(&'static str, &'static str)]) -> String {
-        let mut result = String::new();
-
-        // I the parameter list is empty, do no work.
-        if p.is_empty() {
-            return result;
-        }
-
-        // Otherwise, construct the result by appending parameters one after another
+    pub fn join_params<K: AsRef<str>, V: AsRef<str>>(p: &[(K, V)]) -> String {
+        // Construct the result by appending parameters one after another
         // (i.e. {key}={value}{"&" | ""}).
         // The result has the form: k0=v0&k1=v1&k2=v2
-        let mut it = 1usize;
-        let end = p.len();
-        for (k, v) in p.iter() {
+        let mut result = String::new();
+        for (i, (k, v)) in p.iter().enumerate() {
+            let (k, v) = (k.as_ref(), v.as_ref());
             assert!(!k.is_empty());
             assert!(!v.is_empty());
+
+            // Avoid pushing a leading '&' before the first parameter pair.
+            if i > 0 {
+                result.push('&');
+            }
             result.push_str(k);
             result.push('=');
             result.push_str(v);
+        }
+        result
+    }
 
-            // Avoid pushing a trailing '&' if there are no more parameter pairs.
-            if it < end {
-                result.push('&');
+    /// Set the parameter `k` to the value `v`, replacing the value of `k`
+    /// in-place if it has already been set. This is used by the typed
+    /// building functions, whose values are valid by construction.
+    ///
+    /// If `k` has been set more than once (i.e. through `Url::param()`),
+    /// the first occurrence is replaced and the others are removed.
+    pub(crate) fn set_param(&mut self, k: &str, v: String) {
+        match self.params.iter().position(|(key, _)| key == k) {
+            Some(i) => {
+                self.params[i].1 = v;
+                let rest = self.params.split_off(i + 1);
+                self.params
+                    .extend(rest.into_iter().filter(|(key, _)| key != k));
             }
-            it += 1;
+            None => self.params.push((String::from(k), v)),
+        }
+        self.discard_signature();
+    }
+
+    /// Set the blend, replacing any blend set previously, including any
+    /// `blend` parameter (i.e. that of a parsed `Url`).
+    pub(crate) fn set_blend(&mut self, blend: String) {
+        self.params.retain(|(k, _)| k != "blend");
+        self.blend = Some(blend);
+        self.discard_signature();
+    }
+
+    /// Check that the parameter `k` is not set by a typed value attached to
+    /// this `Url`, which would otherwise be set twice when joined.
+    fn check_attached(&self, k: &str) -> Result<()> {
+        if self.blend.is_some() && k == "blend" {
+            return Err(Error::ParamError(format!(
+                "`{}` is already set by `Url::blend_color()` or `Url::blend_image()`",
+                k
+            )));
         }
-        return result;
+
+        if self.text.is_some() && is_text_key(k) {
+            return Err(Error::ParamError(format!(
+                "`{}` is already set by `Url::text()`",
+                k
+            )));
+        }
+
+        if self.mark.is_some() && is_mark_key(k) {
+            return Err(Error::ParamError(format!(
+                "`{}` is already set by `Url::mark()`",
+                k
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Discard the signature, and the raw path and query, of a parsed `Url`
+    /// once it has been modified.
+    fn discard_signature(&mut self) {
+        self.signature = None;
+        self.raw = None;
+    }
+
+    /// Set the text overlay, replacing any overlay set previously,
+    /// including any `txt*` parameters (i.e. those of a parsed `Url`).
+    pub(crate) fn set_text(&mut self, text: TextOverlay) {
+        self.params.retain(|(k, _)| !is_text_key(k));
+        self.text = Some(text);
+        self.discard_signature();
+    }
+
+    /// Set the watermark, replacing any watermark set previously,
+    /// including any `mark*` parameters (i.e. those of a parsed `Url`).
+    pub(crate) fn set_mark(&mut self, mark: Watermark) {
+        self.params.retain(|(k, _)| !is_mark_key(k));
+        self.mark = Some(mark);
+        self.discard_signature();
+    }
+
+    /// Join a list of key-value parameter pairs, encoding each key and
+    /// value first if this `Url` has encoding turned on.
+    ///
+    /// The values of base64 parameter variants (i.e. `txt64`) are always
+    /// base64 encoded, rather than percent-encoded.
+    fn join_query_params<K: AsRef<str>, V: AsRef<str>>(&self, p: &[(K, V)]) -> String {
+        if self.encode {
+            Self::join_encoded_params(p)
+        } else {
+            let encoded: Vec<(&str, String)> = p
+                .iter()
+                .map(|(k, v)| (k.as_ref(), v.as_ref()))
+                .map(|(k, v)| {
+                    if encode::is_base64_key(k) {
+                        (k, encode::base64(v))
+                    } else {
+                        (k, String::from(v))
+                    }
+                })
+                .collect();
+            Self::join_params(&encoded)
+        }
+    }
+
+    /// Join a list of key-value parameter pairs, encoding each key and
+    /// value first whether or not this `Url` has encoding turned on.
+    fn join_encoded_params<K: AsRef<str>, V: AsRef<str>>(p: &[(K, V)]) -> String {
+        let encoded: Vec<(String, String)> = p
+            .iter()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+            .map(|(k, v)| {
+                if encode::is_base64_key(k) {
+                    (encode::component(k), encode::base64(v))
+                } else {
+                    (encode::component(k), encode::component(v))
+                }
+            })
+            .collect();
+        Self::join_params(&encoded)
     }
 
     pub fn get_scheme(&self) -> &Scheme {
@@ -352,26 +903,37 @@ impl Url {
         &self.lib
     }
 
-    pub fn get_params(&self) -> &[(&'static str, &'static str)] {
+    pub fn get_params(&self) -> &[(String, String)] {
         &self.params
     }
 
     pub fn get_path(&self) -> &str {
         match &self.path {
-            Some(p) => &p,
+            Some(p) => p,
+            None => "",
+        }
+    }
+
+    pub fn get_signature(&self) -> &str {
+        match &self.signature {
+            Some(s) => s,
             None => "",
         }
     }
 
+    pub fn get_path_kind(&self) -> PathKind {
+        PathKind::of(self.get_path())
+    }
+
     pub fn get_token(&self) -> &str {
         match &self.token {
-            Some(t) => &t,
+            Some(t) => t,
             None => "",
         }
     }
 
     pub fn has_params(&self) -> bool {
-        self.params.len() > 0usize
+        !self.params.is_empty()
     }
 
     pub fn to_srcset(&self) -> String {
@@ -379,6 +941,269 @@ impl Url {
     }
 }
 
+impl FromStr for Url {
+    type Err = Error;
+
+    /// Parse an existing imgix URL (i.e. "https://example.imgix.net/image.png?w=320").
+    ///
+    /// The `path` and `params` are decoded, an `ixlib` parameter becomes
+    /// the `Url`'s `lib` and an `s` parameter becomes its signature. The
+    /// remaining parameters are kept in the order they appear. Canonical
+    /// URLs (i.e. those produced by `Url::join()`) round-trip, so that
+    /// `url.parse::<Url>()?.join() == url`. Signed URLs always round-trip,
+    /// even if they were not produced by `Url::join()` (i.e. `ixlib` last),
+    /// so that their signature still matches.
+    ///
+    /// A parsed signature is only valid for the `Url` as it was parsed.
+    /// Modifying the `Url` discards it, set a `token` to re-sign the `Url`.
+    ///
+    /// # Examples
+    /// ```
+    /// use imgix::Url;
+    ///
+    /// let left = "https://example.domain.net/image.png?w=320&auto=format";
+    /// let url: Url = left.parse().unwrap();
+    /// assert_eq!(url.join(), left);
+    ///
+    /// let url = url.param("fit", "crop");
+    /// let right = "https://example.domain.net/image.png?w=320&auto=format&fit=crop";
+    /// assert_eq!(url.join(), right);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns an `Error::ParseError` if the scheme is not
+    /// one of `https` or `http`, if the query is malformed or has an empty
+    /// signature, or if any
+    /// component cannot be decoded. Missing components are reported as
+    /// they are by `Url::try_new()` and `Url::try_path()`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (scheme, rest) = if let Some(rest) = s.strip_prefix("https://") {
+            (Scheme::Https, rest)
+        } else if let Some(rest) = s.strip_prefix("http://") {
+            (Scheme::Http, rest)
+        } else {
+            return Err(Error::ParseError(format!(
+                "`{}` must begin with `https://` or `http://`",
+                s
+            )));
+        };
+
+        let (domain, rest) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i + 1..]),
+            None => (rest, ""),
+        };
+
+        let (path, query) = match rest.find('?') {
+            Some(i) => (&rest[..i], &rest[i + 1..]),
+            None => (rest, ""),
+        };
+
+        let mut url = Url::try_new(domain)?
+            .scheme(scheme)
+            .try_path(&encode::decode(path)?)?;
+
+        let mut signature = None;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (k, v) = match pair.find('=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => {
+                    return Err(Error::ParseError(format!(
+                        "`{}` is not a key-value pair",
+                        pair
+                    )))
+                }
+            };
+
+            let k = encode::decode(k)?;
+            match k.as_str() {
+                "ixlib" => url.lib = String::from(pair),
+                "s" if v.is_empty() => {
+                    return Err(Error::ParseError("`s` cannot be empty".to_owned()))
+                }
+                "s" => signature = Some(String::from(v)),
+                _ if encode::is_base64_key(&k) => {
+                    url = url.try_param(&k, &encode::decode_base64(v)?)?
+                }
+                _ => url = url.try_param(&k, &encode::decode(v)?)?,
+            }
+        }
+
+        // The signature is assigned last, setting `params` discards it.
+        url.signature = signature;
+
+        // A signature only matches the path and query as they were signed.
+        // If joining would not reproduce them (i.e. `ixlib` is not first),
+        // they are kept as-is until the `Url` is modified.
+        if url.signature.is_some() && url.try_join().ok().as_deref() != Some(s) {
+            url.raw = Some(format!("/{}", rest));
+        }
+        Ok(url)
+    }
+}
+
+impl TryFrom<&str> for Url {
+    type Error = Error;
+
+    /// Parse an existing imgix URL, see `Url::from_str()`.
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+/// The outcome of successfully verifying a URL, see `verify()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signature {
+    /// The URL carries an `s` parameter that matches the token.
+    Valid,
+    /// The URL does not carry an `s` parameter.
+    Missing,
+}
+
+/// Verify the signature (i.e. the `s` parameter) of an imgix URL string
+/// against a signing `token`.
+///
+/// The signature is recomputed over the path and query _exactly_ as they
+/// appear in `url`, less the `s` parameter itself, so the URL should be
+/// passed as it was received (i.e. without decoding or reordering).
+///
+/// # Examples
+/// ```
+/// use imgix::{verify, Error, Signature};
+///
+/// const TOKEN: &str = "FOO123bar";
+///
+/// let url = "https://my-social-network.imgix.net/users/1.png?s=6797c24146142d5b40bde3141fd3600c";
+/// assert_eq!(verify(url, TOKEN).unwrap(), Signature::Valid);
+///
+/// let url = "https://my-social-network.imgix.net/users/1.png";
+/// assert_eq!(verify(url, TOKEN).unwrap(), Signature::Missing);
+///
+/// let url = "https://my-social-network.imgix.net/users/2.png?s=6797c24146142d5b40bde3141fd3600c";
+/// assert!(matches!(verify(url, TOKEN), Err(Error::SignatureError(_))));
+/// ```
+///
+/// # Errors
+///
+/// This function returns an `Error::SignatureError` if the signature does
+/// not match (i.e. the URL has been tampered with or was signed with a
+/// different token) and an `Error::ParseError` if `url` is not an
+/// `https` or `http` URL with a path.
+pub fn verify(url: &str, token: &str) -> Result<Signature> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| {
+            Error::ParseError(format!("`{}` must begin with `https://` or `http://`", url))
+        })?;
+
+    // A fragment is never sent to the server, so it is not signed.
+    let rest = match rest.find('#') {
+        Some(i) => &rest[..i],
+        None => rest,
+    };
+
+    // The path keeps its leading slash, as it does when signing.
+    let path_and_query = match rest.find('/') {
+        Some(i) => &rest[i..],
+        None => return Err(Error::ParseError(format!("`{}` has no path", url))),
+    };
+
+    let (path, query) = match path_and_query.find('?') {
+        Some(i) => (&path_and_query[..i], &path_and_query[i + 1..]),
+        None => (path_and_query, ""),
+    };
+
+    let mut signature = None;
+    let mut signed = Vec::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        match pair.strip_prefix("s=") {
+            Some(s) => signature = Some(s),
+            None => signed.push(pair),
+        }
+    }
+
+    match signature {
+        None => Ok(Signature::Missing),
+        Some(s) => {
+            let expected = sign(token, path, &signed.join("&"));
+            if constant_time_eq(expected.as_bytes(), s.to_ascii_lowercase().as_bytes()) {
+                Ok(Signature::Valid)
+            } else {
+                Err(Error::SignatureError(format!(
+                    "the signature of `{}` does not match",
+                    url
+                )))
+            }
+        }
+    }
+}
+
+/// Compare two byte strings in time proportional to their length, rather
+/// than to the length of their common prefix, so that comparing signatures
+/// does not leak how much of a forged signature is correct.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Whether `k` is a parameter of a `TextOverlay` (i.e. `txt`, `txt-font`).
+fn is_text_key(k: &str) -> bool {
+    k == "txt" || k == "txt64" || k.starts_with("txt-")
+}
+
+/// Whether `k` is a parameter of a `Watermark` (i.e. `mark`, `mark-pad`).
+fn is_mark_key(k: &str) -> bool {
+    k == "mark" || k == "mark64" || k.starts_with("mark-")
+}
+
+/// Compute the signature of a `path` and `query` string given a `token`.
+///
+/// The `path` is expected to carry its leading slash (i.e. "/image.png")
+/// and the `query` is expected to be free of its leading "?". The query
+/// is only included in the signed input if it is non-empty.
+fn sign(token: &str, path: &str, query: &str) -> String {
+    let mut input = String::with_capacity(token.len() + path.len() + query.len() + 1);
+    input.push_str(token);
+    input.push_str(path);
+
+    if !query.is_empty() {
+        input.push('?');
+        input.push_str(query);
+    }
+    format!("{:x}", md5::compute(input))
+}
+
+/// The kind of `path` a `Url` has been given.
+///
+/// Most sources serve images relative to their origin. Web Proxy sources
+/// instead take the fully-qualified URL of the origin image as their path,
+/// i.e. `https://example.com/image.png`. Because a Web Proxy source can
+/// fetch _any_ image, imgix requires these `Url`s to be signed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathKind {
+    /// A path relative to the source's origin, i.e. "images/image.png".
+    Relative,
+    /// A fully-qualified `http(s)://` URL fetched by a Web Proxy source.
+    WebProxy,
+}
+
+impl PathKind {
+    /// Determine the kind of the path `p`.
+    ///
+    /// A path is a Web Proxy path if it begins with `http://` or `https://`,
+    /// either as-is or already percent-encoded (i.e. `http%3A%2F%2F`).
+    pub fn of(p: &str) -> Self {
+        const PREFIXES: [&str; 4] = ["http://", "https://", "http%3a%2f%2f", "https%3a%2f%2f"];
+
+        let p = p.to_ascii_lowercase();
+        if PREFIXES.iter().any(|prefix| p.starts_with(prefix)) {
+            PathKind::WebProxy
+        } else {
+            PathKind::Relative
+        }
+    }
+}
+
 /// Primary value for expressing which scheme a url uses.
 ///
 /// This is an enum to define and enforce the crate semantics of what
@@ -392,7 +1217,7 @@ impl Url {
 /// it also has the added benefit of being _discoverable_. When usage is
 /// `url.scheme(Scheme::...)`, the range of possible schemes can be discovered
 /// by IDE code completion tools.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Scheme {
     Https,
     Http,
@@ -401,8 +1226,8 @@ pub enum Scheme {
 impl Display for Scheme {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Scheme::Https => write!(f, "{}", "https"),
-            Scheme::Http => write!(f, "{}", "http"),
+            Scheme::Https => write!(f, "https"),
+            Scheme::Http => write!(f, "http"),
         }
     }
 }
@@ -417,6 +1242,7 @@ mod test {
     const PNG_PATH: &str = "images/test-image.png";
     const JPG_PATH: &str = "images/test-image.jpg";
     const BASIC_PARAMS: &[(&str, &str)] = &[("w", "640"), ("h", "720"), ("fit", "crop")];
+    const TOKEN: &str = "FOO123bar";
 
     #[test]
     fn test_join_params() {
@@ -439,7 +1265,11 @@ mod test {
         assert_eq!(default.lib, "".to_owned());
         assert_eq!(default.params, vec![]);
         assert_eq!(default.path, None);
+        assert_eq!(default.text, None);
+        assert_eq!(default.mark, None);
         assert_eq!(default.token, None);
+        assert_eq!(default.signature, None);
+        assert!(default.encode);
     }
 
     #[test]
@@ -521,6 +1351,37 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_assign_runtime_params() {
+        let w = (320 * 2).to_string();
+        let crop = format!("{},{},{},{}", 0, 0, w, 360);
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .param("w", &w)
+            .params(&[("rect", crop.as_str()), ("fit", "crop")]);
+
+        assert_eq!(
+            url.get_params(),
+            &[
+                ("w".to_owned(), "640".to_owned()),
+                ("rect".to_owned(), "0,0,640,360".to_owned()),
+                ("fit".to_owned(), "crop".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assign_owned_params() {
+        let params: Vec<(String, String)> = BASIC_PARAMS
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let url = Url::new(DOMAIN).path(PNG_PATH).params(&params);
+        let right = "https://test.domain.com/images/test-image.png?w=640&h=720&fit=crop";
+        assert_eq!(url.join(), right);
+    }
+
     #[test]
     #[should_panic]
     fn test_assign_params_mismatch() {
@@ -582,4 +1443,540 @@ mod test {
         assert!(url.params.is_empty());
         assert_eq!(url.join(), right);
     }
+
+    #[test]
+    fn test_encode_path() {
+        let url = Url::new(DOMAIN).path("images/my image #1?.png");
+        let right = "https://test.domain.com/images/my%20image%20%231%3F.png";
+        assert_eq!(url.join(), right);
+
+        let url = Url::new(DOMAIN).path("images/ساندویچ.jpg");
+        let right = "https://test.domain.com/images/%D8%B3%D8%A7%D9%86%D8%AF%D9%88%DB%8C%DA%86.jpg";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_encode_params() {
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .params(&[("ar", "16:9"), ("txt", "Hello, World & Friends!")]);
+
+        let right = "https://test.domain.com/images/test-image.png?ar=16%3A9&txt=Hello%2C%20World%20%26%20Friends!";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_encode_base64_params() {
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .params(&[("txt64", "Hello 🌎 & Friends"), ("txt-size", "48")]);
+
+        let right = "https://test.domain.com/images/test-image.png?txt64=SGVsbG8g8J-MjiAmIEZyaWVuZHM&txt-size=48";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_encode_disabled() {
+        let url = Url::new(DOMAIN)
+            .path("images/my%20image.png")
+            .param("ar", "16%3A9")
+            .encode(false);
+
+        let right = "https://test.domain.com/images/my%20image.png?ar=16%3A9";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_encode_disabled_base64_params() {
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .params(&[("txt64", "Hello 🌎 & Friends"), ("txt-size", "48")])
+            .encode(false);
+
+        let right = "https://test.domain.com/images/test-image.png?txt64=SGVsbG8g8J-MjiAmIEZyaWVuZHM&txt-size=48";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Url::try_new(DOMAIN).is_ok());
+        assert!(matches!(Url::try_new(""), Err(Error::DomainError(_))));
+    }
+
+    #[test]
+    fn test_try_domain() {
+        let url = Url::new(DOMAIN).try_domain(DOMAIN2).unwrap();
+        assert_eq!(url.domain, DOMAIN2);
+        assert!(matches!(
+            Url::new(DOMAIN).try_domain(""),
+            Err(Error::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_path() {
+        let url = Url::new(DOMAIN).try_path(PNG_PATH).unwrap();
+        assert_eq!(url.path, Some(PNG_PATH.to_owned()));
+        assert!(matches!(
+            Url::new(DOMAIN).try_path(""),
+            Err(Error::PathError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_param() {
+        let url = Url::new(DOMAIN).try_param("w", "320").unwrap();
+        assert_eq!(url.params, vec![("w".to_owned(), "320".to_owned())]);
+        assert!(matches!(
+            Url::new(DOMAIN).try_param("", "320"),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN).try_param("w", ""),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_params() {
+        let url = Url::new(DOMAIN).try_params(BASIC_PARAMS).unwrap();
+        assert_eq!(url.params.len(), BASIC_PARAMS.len());
+        assert!(matches!(
+            Url::new(DOMAIN).try_params(&[("w", "320"), ("h", "")]),
+            Err(Error::ParamError(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_join() {
+        let url = Url::new(DOMAIN).path(PNG_PATH);
+        assert_eq!(url.try_join().unwrap(), url.join());
+        assert!(matches!(
+            Url::new(DOMAIN).try_join(),
+            Err(Error::JoinError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN).path("http://a.com/image.png").try_join(),
+            Err(Error::JoinError(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_join_without_path() {
+        let _ = Url::new(DOMAIN).join();
+    }
+
+    #[test]
+    fn test_parse_components() {
+        let url: Url = "http://testing.imgix.net/images/my%20image.png?ixlib=rust-0.1.3&ar=16%3A9&txt64=SGVsbG8sIFdvcmxkIQ&s=abc123"
+            .parse()
+            .unwrap();
+
+        assert_eq!(url.scheme, Scheme::Http);
+        assert_eq!(url.domain, "testing.imgix.net");
+        assert_eq!(url.path, Some("images/my image.png".to_owned()));
+        assert_eq!(url.lib, "ixlib=rust-0.1.3");
+        assert_eq!(
+            url.params,
+            vec![
+                ("ar".to_owned(), "16:9".to_owned()),
+                ("txt64".to_owned(), "Hello, World!".to_owned()),
+            ]
+        );
+        assert_eq!(url.signature, Some("abc123".to_owned()));
+        assert_eq!(url.token, None);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let urls = [
+            "https://test.domain.com/images/test-image.png",
+            "http://test.domain.com/images/test-image.png?w=320&h=640&fit=crop",
+            "https://test.domain.com/images/my%20image%20%231%3F.png?ar=16%3A9&txt=Hello%2C%20World%20%26%20Friends!",
+            "https://test.domain.com/images/test-image.png?txt64=SGVsbG8g8J-MjiAmIEZyaWVuZHM&txt-size=48",
+            "https://testing.imgix.net/images/demo.png?ixlib=rust-0.1.3&w=400&s=a0dcb83e19b070ad355e27e7890778b8",
+            "https://my-social-network.imgix.net/http%3A%2F%2Favatars.com%2Fjohn-smith.png?s=493a52f008c91416351f8b33d4883135",
+        ];
+
+        for left in urls.iter() {
+            let url = Url::try_from(*left).unwrap();
+            assert_eq!(url.join(), *left);
+        }
+    }
+
+    #[test]
+    fn test_join_unsupported_combination() {
+        // Combinations are reported by `validate()`, not by `join()`.
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .param("fm", "jpg")
+            .param("loop", "0");
+        let right = "https://test.domain.com/images/test-image.png?fm=jpg&loop=0";
+        assert_eq!(url.join(), right);
+        assert!(matches!(url.validate(), Err(Error::ParamError(_))));
+
+        let left = "https://test.domain.com/image.png?corner-radius=8&prefix=hero";
+        let url: Url = left.parse().unwrap();
+        assert_eq!(url.join(), left);
+        assert!(url.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_non_canonical_signed() {
+        // Neither of these is produced by `join()`, which puts `ixlib` first
+        // and encodes `:`.
+        let urls = [("/i.png", "w=1&ixlib=rust-1"), ("/i.png", "ar=16:9")];
+
+        for (path, query) in urls.iter() {
+            let left = format!(
+                "https://test.domain.com{}?{}&s={}",
+                path,
+                query,
+                sign(TOKEN, path, query)
+            );
+            let url: Url = left.parse().unwrap();
+            assert_eq!(url.join(), left);
+            assert_eq!(verify(&url.join(), TOKEN).unwrap(), Signature::Valid);
+
+            // Once modified, the `Url` is joined canonically and unsigned.
+            let url = url.param("h", "2");
+            assert!(!url.join().contains("&s="));
+            assert_eq!(verify(&url.join(), TOKEN).unwrap(), Signature::Missing);
+        }
+    }
+
+    #[test]
+    fn test_parse_and_sign() {
+        let left = "https://testing.imgix.net/images/demo.png?w=400&s=stale";
+        let url: Url = left.parse::<Url>().unwrap().param("h", "300").token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/demo.png?w=400&h=300&s=d608c68c3ab63b5fc943815a771768ad";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_parse_and_modify() {
+        let left =
+            "https://testing.imgix.net/images/demo.png?w=400&s=d608c68c3ab63b5fc943815a771768ad";
+        let url = left.parse::<Url>().unwrap().param("h", "300");
+
+        // The parsed signature no longer applies.
+        let right = "https://testing.imgix.net/images/demo.png?w=400&h=300";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            "ftp://test.domain.com/image.png".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            "test.domain.com/image.png".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            "https:///image.png".parse::<Url>(),
+            Err(Error::DomainError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com".parse::<Url>(),
+            Err(Error::PathError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com/image.png?w".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com/image.png?w=".parse::<Url>(),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com/image%zz.png".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com/image.png?txt64=*".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            "https://test.domain.com/image.png?s=".parse::<Url>(),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_valid() {
+        let urls = [
+            "https://my-social-network.imgix.net/users/1.png?s=6797c24146142d5b40bde3141fd3600c",
+            "http://testing.imgix.net/images/demo.png?ixlib=rust-0.1.3&w=400&s=a0dcb83e19b070ad355e27e7890778b8",
+            "https://testing.imgix.net/images/my%20image%20%231%3F.png?txt=Hello%2C%20World%20%26%20Friends!&s=7723568990c191cc18542f0c4642852a",
+            "https://my-social-network.imgix.net/http%3A%2F%2Favatars.com%2Fjohn-smith.png?s=493A52F008C91416351F8B33D4883135",
+            "https://my-social-network.imgix.net/users/1.png?s=6797c24146142d5b40bde3141fd3600c#x",
+        ];
+
+        for url in urls.iter() {
+            assert_eq!(verify(url, TOKEN).unwrap(), Signature::Valid);
+        }
+    }
+
+    #[test]
+    fn test_verify_joined() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/my image.png")
+            .params(&[("ar", "16:9"), ("txt64", "Hello 🌎")])
+            .lib("ixlib=rust-0.1.3")
+            .token(TOKEN);
+
+        assert_eq!(verify(&url.join(), TOKEN).unwrap(), Signature::Valid);
+    }
+
+    #[test]
+    fn test_verify_missing() {
+        let url = "https://testing.imgix.net/images/demo.png?w=400&h=300";
+        assert_eq!(verify(url, TOKEN).unwrap(), Signature::Missing);
+    }
+
+    #[test]
+    fn test_verify_tampered() {
+        let urls = [
+            // A parameter has been changed.
+            "https://testing.imgix.net/images/demo.png?w=4000&h=300&s=d608c68c3ab63b5fc943815a771768ad",
+            // A parameter has been added.
+            "https://testing.imgix.net/images/demo.png?w=400&h=300&blur=100&s=d608c68c3ab63b5fc943815a771768ad",
+            // The path has been changed.
+            "https://testing.imgix.net/images/demo.jpg?w=400&h=300&s=d608c68c3ab63b5fc943815a771768ad",
+            // The signature has been changed.
+            "https://testing.imgix.net/images/demo.png?w=400&h=300&s=d608c68c3ab63b5fc943815a771768a",
+        ];
+
+        for url in urls.iter() {
+            assert!(matches!(verify(url, TOKEN), Err(Error::SignatureError(_))));
+        }
+
+        let url = "https://testing.imgix.net/images/demo.png?w=400&h=300&s=d608c68c3ab63b5fc943815a771768ad";
+        assert!(matches!(
+            verify(url, "BAR123foo"),
+            Err(Error::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_malformed() {
+        assert!(matches!(
+            verify("ftp://testing.imgix.net/image.png?s=abc", TOKEN),
+            Err(Error::ParseError(_))
+        ));
+        assert!(matches!(
+            verify("https://testing.imgix.net", TOKEN),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    const EPOCH_1_6: u64 = 1_600_000_000;
+
+    fn fixed_clock() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(EPOCH_1_6)
+    }
+
+    #[test]
+    fn test_expires() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/demo.png")
+            .param("w", "400")
+            .expires(fixed_clock())
+            .token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/demo.png?w=400&expires=1600000000&s=6c248a06e10ca1504fb90318cada8f0b";
+        assert_eq!(url.join(), right);
+        assert_eq!(verify(&url.join(), TOKEN).unwrap(), Signature::Valid);
+    }
+
+    #[test]
+    fn test_expires_in_with() {
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .expires_in_with(Duration::from_secs(60), &fixed_clock)
+            .token(TOKEN);
+
+        assert_eq!(
+            url.get_params(),
+            &[("expires".to_owned(), (EPOCH_1_6 + 60).to_string())]
+        );
+    }
+
+    #[test]
+    fn test_expires_replaces() {
+        let url = Url::new(DOMAIN)
+            .path(PNG_PATH)
+            .param("w", "320")
+            .expires_in_with(Duration::from_secs(60), &fixed_clock)
+            .param("h", "640")
+            .expires_in_with(Duration::from_secs(120), &fixed_clock);
+
+        assert_eq!(
+            url.get_params(),
+            &[
+                ("w".to_owned(), "320".to_owned()),
+                ("expires".to_owned(), (EPOCH_1_6 + 120).to_string()),
+                ("h".to_owned(), "640".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expires_in() {
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let url = Url::new(DOMAIN).expires_in(Duration::from_secs(60));
+        let after = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let expires: u64 = url.get_params()[0].1.parse().unwrap();
+        assert!(before + 60 <= expires && expires <= after + 60);
+    }
+
+    #[test]
+    fn test_expires_errors() {
+        assert!(matches!(
+            Url::new(DOMAIN).try_expires(UNIX_EPOCH - Duration::from_secs(1)),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN).try_expires_in_with(Duration::MAX, &fixed_clock),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN).try_expires_in(Duration::MAX),
+            Err(Error::ParamError(_))
+        ));
+        assert!(matches!(
+            Url::new(DOMAIN)
+                .path(PNG_PATH)
+                .expires(fixed_clock())
+                .try_join(),
+            Err(Error::JoinError(_))
+        ));
+    }
+
+    #[test]
+    fn test_path_kind() {
+        assert_eq!(PathKind::of(PNG_PATH), PathKind::Relative);
+        assert_eq!(PathKind::of("http-images/image.png"), PathKind::Relative);
+        assert_eq!(PathKind::of("http://a.com/image.png"), PathKind::WebProxy);
+        assert_eq!(PathKind::of("HTTPS://a.com/image.png"), PathKind::WebProxy);
+        assert_eq!(PathKind::of("https%3A%2F%2Fa.com"), PathKind::WebProxy);
+    }
+
+    #[test]
+    fn test_web_proxy() {
+        let url = Url::new("my-social-network.imgix.net")
+            .path("http://avatars.com/john-smith.png")
+            .token(TOKEN);
+
+        let right = "https://my-social-network.imgix.net/http%3A%2F%2Favatars.com%2Fjohn-smith.png?s=493a52f008c91416351f8b33d4883135";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_web_proxy_params() {
+        let url = Url::new("my-social-network.imgix.net")
+            .path("https://avatars.com/john smith.png?size=large")
+            .param("w", "400")
+            .token(TOKEN);
+
+        let right = "https://my-social-network.imgix.net/https%3A%2F%2Favatars.com%2Fjohn%20smith.png%3Fsize%3Dlarge?w=400&s=0021377f5a11eeeb27bd52de8d361e2a";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_web_proxy_encoded() {
+        let url = Url::new("my-social-network.imgix.net")
+            .path("http%3A%2F%2Favatars.com%2Fjohn-smith.png")
+            .token(TOKEN)
+            .encode(false);
+
+        let right = "https://my-social-network.imgix.net/http%3A%2F%2Favatars.com%2Fjohn-smith.png?s=493a52f008c91416351f8b33d4883135";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_web_proxy_unsigned() {
+        let _ = Url::new(DOMAIN)
+            .path("http://avatars.com/john-smith.png")
+            .join();
+    }
+
+    #[test]
+    fn test_sign_path() {
+        let url = Url::new("my-social-network.imgix.net")
+            .path("users/1.png")
+            .token(TOKEN);
+
+        let right =
+            "https://my-social-network.imgix.net/users/1.png?s=6797c24146142d5b40bde3141fd3600c";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_sign_path_and_params() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/demo.png")
+            .params(&[("w", "400"), ("h", "300")])
+            .token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/demo.png?w=400&h=300&s=d608c68c3ab63b5fc943815a771768ad";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_sign_lib() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/demo.png")
+            .lib("ixlib=rust-0.1.3")
+            .token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/demo.png?ixlib=rust-0.1.3&s=ff1ac534a202a2151dfb6e4a27fb89f2";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_sign_lib_and_params() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/demo.png")
+            .lib("ixlib=rust-0.1.3")
+            .param("w", "400")
+            .token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/demo.png?ixlib=rust-0.1.3&w=400&s=a0dcb83e19b070ad355e27e7890778b8";
+        assert_eq!(url.join(), right);
+    }
+
+    #[test]
+    fn test_sign_join_with() {
+        let url = Url::new("my-social-network.imgix.net")
+            .path("users/1.png")
+            .param("w", "320")
+            .token(TOKEN);
+
+        let right = "https://my-social-network.imgix.net/users/1.png?w=320&dpr=1&s=ad84544be64c8aff6de5b755123a794e";
+        assert_eq!(url.join_with(&[("dpr", "1")]), right);
+    }
+
+    #[test]
+    fn test_sign_encoded() {
+        let url = Url::new("testing.imgix.net")
+            .path("images/my image #1?.png")
+            .param("txt", "Hello, World & Friends!")
+            .token(TOKEN);
+
+        let right = "https://testing.imgix.net/images/my%20image%20%231%3F.png?txt=Hello%2C%20World%20%26%20Friends!&s=7723568990c191cc18542f0c4642852a";
+        assert_eq!(url.join(), right);
+    }
 }
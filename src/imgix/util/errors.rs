@@ -9,7 +9,10 @@ pub enum Error {
     DomainError(String),
     JoinError(String),
     ParamError(String),
+    ParseError(String),
     PathError(String),
+    RangeError(String),
+    SignatureError(String),
 }
 
 impl From<io::Error> for Error {
@@ -18,17 +21,26 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::ParseError(err.to_string())
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // TODO: there may be a macro opportunity here...
         match self {
             Error::Io(e) => write!(f, "{error}: {msg}", error = stringify!(Error::Io), msg = e),
             Error::DomainError(msg) => {
-                write!(f, "{error}: {msg}", error = "DomainError", msg = msg)
+                write!(f, "DomainError: {msg}", msg = msg)
             }
-            Error::JoinError(msg) => write!(f, "{error}: {msg}", error = "JoinError", msg = msg),
-            Error::PathError(msg) => write!(f, "{error}: {msg}", error = "PathError", msg = msg),
-            Error::ParamError(msg) => write!(f, "{error}: {msg}", error = "ParamError", msg = msg),
+            Error::JoinError(msg) => write!(f, "JoinError: {msg}", msg = msg),
+            Error::PathError(msg) => write!(f, "PathError: {msg}", msg = msg),
+            Error::ParamError(msg) => write!(f, "ParamError: {msg}", msg = msg),
+            Error::ParseError(msg) => write!(f, "ParseError: {msg}", msg = msg),
+            Error::RangeError(msg) => write!(f, "RangeError: {msg}", msg = msg),
+            Error::SignatureError(msg) => write!(f, "SignatureError: {msg}", msg = msg),
         }
     }
 }
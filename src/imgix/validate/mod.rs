@@ -1,3 +1,6 @@
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
 use crate::util::errors::Error::*;
 use crate::Result;
 
@@ -28,3 +31,44 @@ pub fn param_pair(k: &str, v: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Validate that the value `v` of the parameter `k` is within `range`.
+///
+/// Values that cannot be compared (i.e. `NaN`) are never within range.
+pub fn in_range<T: PartialOrd + Display>(k: &str, v: T, range: RangeInclusive<T>) -> Result<()> {
+    if !range.contains(&v) {
+        return Err(RangeError(format!(
+            "`{}` must be in {}..={}, found {}",
+            k,
+            range.start(),
+            range.end(),
+            v
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate that the value `v` of the parameter `k` is a non-negative,
+/// finite number.
+pub fn non_negative(k: &str, v: f32) -> Result<()> {
+    // `NaN` is not finite either.
+    if !v.is_finite() || v < 0.0 {
+        return Err(RangeError(format!(
+            "`{}` must be a non-negative number, found {}",
+            k, v
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate that the value `v` of the parameter `k` is positive (i.e. a
+/// page or frame number, counting from 1).
+pub fn positive(k: &str, v: u32) -> Result<()> {
+    if v == 0 {
+        return Err(RangeError(format!("`{}` must be positive, found 0", k)));
+    }
+
+    Ok(())
+}